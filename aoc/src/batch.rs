@@ -0,0 +1,257 @@
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{duration_as_millis, Report};
+
+/// How often a running input is checked for having finished or timed out
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Answers and timings of a run in a child process
+pub struct BatchReport {
+    pub parse_time: Duration,
+    pub solution_time: Duration,
+    /// Label and value of every answer
+    pub answers: Vec<(String, String)>,
+}
+
+/// Outcome of running a day's solution over a single input file
+pub struct BatchEntry {
+    pub input: PathBuf,
+    pub outcome: Result<BatchReport, String>,
+}
+
+impl BatchEntry {
+    fn total_millis(&self) -> Option<f64> {
+        self.outcome
+            .as_ref()
+            .ok()
+            .map(|r| duration_as_millis(r.parse_time) + duration_as_millis(r.solution_time))
+    }
+}
+
+/// Writes the report for the batch to read back, the timings in nanoseconds on the first two
+/// lines, then every answer's label and value separated by a tab
+pub fn write_report<W: Write>(report: &Report, mut out: W) -> io::Result<()> {
+    writeln!(out, "{}", report.parse_time.as_nanos())?;
+    writeln!(out, "{}", report.solution_time.as_nanos())?;
+    for answer in &report.answers {
+        writeln!(out, "{}\t{}", answer.label, answer.value)?;
+    }
+
+    Ok(())
+}
+
+fn read_report(output: &str) -> Option<BatchReport> {
+    let mut lines = output.lines();
+    let mut duration = || Some(Duration::from_nanos(lines.next()?.parse().ok()?));
+    let parse_time = duration()?;
+    let solution_time = duration()?;
+    let answers = lines
+        .map(|line| {
+            let (label, value) = line.split_once('\t')?;
+            Some((label.to_string(), value.to_string()))
+        })
+        .collect::<Option<_>>()?;
+
+    Some(BatchReport {
+        parse_time,
+        solution_time,
+        answers,
+    })
+}
+
+/// Reads everything from the pipe on another thread, so a chatty child can't block on it
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// Runs the puzzle over a single input in a child process, killing it after the timeout
+fn run_child(year: u16, day: u8, input: &Path, timeout: Duration) -> Result<BatchReport, String> {
+    let mut child = Command::new(env::current_exe().map_err(|e| e.to_string())?)
+        .args(["run", "--batch-report", "--year", &year.to_string()])
+        .args(["--day", &day.to_string(), "--input"])
+        .arg(input)
+        // A backtrace would bury the panic message the error is read from
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("timed out after {}s", timeout.as_secs_f64()));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if status.success() {
+        return read_report(&stdout).ok_or_else(|| "unreadable report".to_string());
+    }
+
+    // The last line that isn't a hint about backtraces says what went wrong
+    let message = stderr
+        .lines()
+        .rev()
+        .find(|line| !line.is_empty() && !line.starts_with("note:"))
+        .unwrap_or_default();
+    match status.code() {
+        Some(1) if !message.is_empty() => Err(message.to_string()),
+        Some(101) => Err(format!("panicked: {}", message)),
+        _ if message.is_empty() => Err(status.to_string()),
+        _ => Err(format!("{}: {}", status, message)),
+    }
+}
+
+/// Runs the puzzle's solution over every file in the directory, in file name order
+///
+/// Each input runs in its own child process, so a solution panicking, overflowing its stack or
+/// running past the timeout on one input doesn't stop the batch.
+pub fn run_batch<T: AsRef<Path>>(
+    year: u16,
+    day: u8,
+    dir: T,
+    timeout: Duration,
+) -> io::Result<Vec<BatchEntry>> {
+    let mut inputs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+
+    let entries = inputs
+        .into_iter()
+        .map(|input| {
+            let outcome = run_child(year, day, &input, timeout);
+
+            BatchEntry { input, outcome }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Returns whether each entry's total runtime is over `factor` times the median runtime
+///
+/// Failed runs are never outliers, and at least three successful runs are needed to tell.
+pub fn find_outliers(entries: &[BatchEntry], factor: f64) -> Vec<bool> {
+    let mut times: Vec<f64> = entries
+        .iter()
+        .filter_map(BatchEntry::total_millis)
+        .collect();
+    if times.len() < 3 {
+        return vec![false; entries.len()];
+    }
+
+    times.sort_by(f64::total_cmp);
+    let median = times[times.len() / 2];
+
+    entries
+        .iter()
+        .map(|e| e.total_millis().is_some_and(|t| t > median * factor))
+        .collect()
+}
+
+/// Prints a table with the answers and timings of every entry, highlighting the outliers
+pub fn print_table(entries: &[BatchEntry], outliers: &[bool]) {
+    // Use the labels of the first successful run as the legend
+    let labels: Vec<&str> = entries
+        .iter()
+        .find_map(|e| e.outcome.as_ref().ok())
+        .map(|r| r.answers.iter().map(|(label, _)| label.as_str()).collect())
+        .unwrap_or_default();
+    for (i, label) in labels.iter().enumerate() {
+        println!("Answer {}: {}", i + 1, label);
+    }
+    println!();
+
+    let mut header = vec!["Input".to_string()];
+    header.extend((1..=labels.len()).map(|i| format!("Answer {}", i)));
+    header.push("Parse (ms)".to_string());
+    header.push("Solution (ms)".to_string());
+
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|e| {
+            let mut row = vec![e
+                .input
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()];
+            match &e.outcome {
+                Ok(report) => {
+                    row.extend(report.answers.iter().map(|(_, value)| value.clone()));
+                    row.push(format!("{:.6}", duration_as_millis(report.parse_time)));
+                    row.push(format!("{:.6}", duration_as_millis(report.solution_time)));
+                }
+                Err(error) => row.push(format!("error: {}", error)),
+            }
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(String::len).collect();
+    for row in &rows {
+        // Errors span the remaining columns, so they don't count towards the widths
+        if row.len() == header.len() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+
+    println!("{}", format_row(&header));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+
+    let colored = io::stdout().is_terminal();
+    for (row, &is_outlier) in rows.iter().zip(outliers) {
+        let line = format_row(row);
+        if !is_outlier {
+            println!("{}", line);
+        } else if colored {
+            println!("\x1b[1;31m{}  <- outlier\x1b[0m", line);
+        } else {
+            println!("{}  <- outlier", line);
+        }
+    }
+}
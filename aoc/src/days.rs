@@ -0,0 +1,41 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use common::check::Problem;
+use common::Report;
+
+pub type Solver = fn(PathBuf) -> Result<Report, Box<dyn Error>>;
+pub type Checker = fn(&str) -> Vec<Problem>;
+
+/// Returns the solver for the given year and day, if it has been solved
+pub fn solver(year: u16, day: u8) -> Option<Solver> {
+    match (year, day) {
+        (2015, 1) => Some(aoc2015_day01::run::<PathBuf>),
+        (2015, 2) => Some(aoc2015_day02::run::<PathBuf>),
+        (2015, 3) => Some(aoc2015_day03::run::<PathBuf>),
+        (2015, 4) => Some(aoc2015_day04::run::<PathBuf>),
+        (2015, 5) => Some(aoc2015_day05::run::<PathBuf>),
+        (2015, 6) => Some(aoc2015_day06::run::<PathBuf>),
+        (2015, 7) => Some(aoc2015_day07::run::<PathBuf>),
+        _ => None,
+    }
+}
+
+/// Returns the input checker for the given year and day, if it has been solved
+pub fn checker(year: u16, day: u8) -> Option<Checker> {
+    match (year, day) {
+        (2015, 1) => Some(aoc2015_day01::check_input),
+        (2015, 2) => Some(aoc2015_day02::check_input),
+        (2015, 3) => Some(aoc2015_day03::check_input),
+        (2015, 4) => Some(aoc2015_day04::check_input),
+        (2015, 5) => Some(aoc2015_day05::check_input),
+        (2015, 6) => Some(aoc2015_day06::check_input),
+        (2015, 7) => Some(aoc2015_day07::check_input),
+        _ => None,
+    }
+}
+
+/// Returns the path of the default input for the given year and day
+pub fn default_input(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("years/{}/day{:02}/input", year, day))
+}
@@ -0,0 +1,136 @@
+mod batch;
+mod days;
+
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io, process};
+
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Identifies a puzzle by its year and day
+#[derive(Args)]
+struct Puzzle {
+    #[arg(long, default_value_t = 2015)]
+    year: u16,
+
+    #[arg(long)]
+    day: u8,
+}
+
+impl Puzzle {
+    fn not_solved(&self) -> String {
+        format!("Day {} of {} is not solved", self.day, self.year)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a puzzle's solution over a single input
+    Run {
+        #[command(flatten)]
+        puzzle: Puzzle,
+
+        /// Input file, defaults to the puzzle's own input
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Print the report for a batch to read back, used when running each input of a batch
+        #[arg(long, hide = true)]
+        batch_report: bool,
+    },
+    /// Check an input against the puzzle's format, without solving
+    Check {
+        #[command(flatten)]
+        puzzle: Puzzle,
+
+        /// Input file, defaults to the puzzle's own input
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Run a puzzle's solution over every input in a directory and compare the results
+    Batch {
+        #[command(flatten)]
+        puzzle: Puzzle,
+
+        /// Directory holding the inputs
+        dir: PathBuf,
+
+        /// Runs taking longer than this many times the median are highlighted
+        #[arg(long, default_value_t = 3.0)]
+        outlier_factor: f64,
+
+        /// Inputs still running after this many seconds are stopped and reported as failed
+        #[arg(long, default_value_t = 60.0)]
+        timeout: f64,
+    },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            puzzle,
+            input,
+            batch_report,
+        } => {
+            let solver = days::solver(puzzle.year, puzzle.day).ok_or(puzzle.not_solved())?;
+            let input = input.unwrap_or_else(|| days::default_input(puzzle.year, puzzle.day));
+
+            if batch_report {
+                // The batch shows the last line of the errors, so keep them on one line
+                match solver(input) {
+                    Ok(report) => batch::write_report(&report, io::stdout().lock())?,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        process::exit(1);
+                    }
+                }
+            } else {
+                let report = solver(input)?;
+                report.print();
+            }
+        }
+        Command::Check { puzzle, input } => {
+            let checker = days::checker(puzzle.year, puzzle.day).ok_or(puzzle.not_solved())?;
+            let input = input.unwrap_or_else(|| days::default_input(puzzle.year, puzzle.day));
+
+            let mut problems = checker(&fs::read_to_string(&input)?);
+            problems.sort_by_key(|p| (p.line, p.column));
+            for problem in &problems {
+                println!("{}:{}", input.display(), problem);
+            }
+
+            if !problems.is_empty() {
+                return Err(format!("Found {} problems in the input", problems.len()).into());
+            }
+            println!("{}: no problems found", input.display());
+        }
+        Command::Batch {
+            puzzle,
+            dir,
+            outlier_factor,
+            timeout,
+        } => {
+            days::solver(puzzle.year, puzzle.day).ok_or(puzzle.not_solved())?;
+            let timeout = Duration::try_from_secs_f64(timeout)
+                .ok()
+                .filter(|timeout| !timeout.is_zero())
+                .ok_or("--timeout must be a positive number of seconds")?;
+
+            let entries = batch::run_batch(puzzle.year, puzzle.day, dir, timeout)?;
+            let outliers = batch::find_outliers(&entries, outlier_factor);
+            batch::print_table(&entries, &outliers);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+/// A problem found in an input file, at a 1-based line and column
+#[derive(Debug)]
+pub struct Problem {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new<T: Into<String>>(line: usize, column: usize, message: T) -> Self {
+        Problem {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Reports CRLF line endings and trailing whitespace on every line of the input
+pub fn check_line_endings(input: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    for (i, line) in input.split('\n').enumerate() {
+        let line = match line.strip_suffix('\r') {
+            Some(stripped) => {
+                problems.push(Problem::new(
+                    i + 1,
+                    stripped.chars().count() + 1,
+                    "CRLF line ending",
+                ));
+                stripped
+            }
+            None => line,
+        };
+
+        let trimmed = line.trim_end();
+        if trimmed.len() < line.len() {
+            problems.push(Problem::new(
+                i + 1,
+                trimmed.chars().count() + 1,
+                "Trailing whitespace",
+            ));
+        }
+    }
+
+    problems
+}
+
+/// Returns the 1-based column at which `field` starts, given it's a subslice of `line`
+pub fn column_of(line: &str, field: &str) -> usize {
+    let offset = field.as_ptr() as usize - line.as_ptr() as usize;
+
+    line[..offset].chars().count() + 1
+}
@@ -0,0 +1,49 @@
+pub mod check;
+pub mod xorshift;
+
+use std::time::Duration;
+
+/// A single labelled answer produced by a day's solution
+pub struct Answer {
+    pub label: &'static str,
+    pub value: String,
+}
+
+impl Answer {
+    pub fn new<T: ToString>(label: &'static str, value: T) -> Self {
+        Answer {
+            label,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Answers and timings from one run of a day's solution over one input
+pub struct Report {
+    pub parse_time: Duration,
+    pub solution_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+impl Report {
+    pub fn print(&self) {
+        println!(
+            "Parsing the input took {:.6}ms\n",
+            duration_as_millis(self.parse_time)
+        );
+
+        println!(
+            "Solution:\nTook {:.6}ms",
+            duration_as_millis(self.solution_time)
+        );
+        for answer in &self.answers {
+            println!("{}: {}", answer.label, answer.value);
+        }
+        println!();
+    }
+}
+
+/// Converts a duration to fractional milliseconds
+pub fn duration_as_millis(duration: Duration) -> f64 {
+    duration.as_millis() as f64 + (duration.subsec_nanos() as f64 * 1e-6).fract()
+}
@@ -0,0 +1,28 @@
+/// Small xorshift generator, for reproducible inputs in benchmarks and tests
+///
+/// Good enough to wander around, not for anything that needs real randomness.
+#[derive(Clone, Debug)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// A zero seed would only ever generate zeros, so it's replaced
+    pub fn new(seed: u64) -> Self {
+        XorShift {
+            state: if seed == 0 { 0x2015 } else { seed },
+        }
+    }
+}
+
+impl Iterator for XorShift {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        Some(self.state)
+    }
+}
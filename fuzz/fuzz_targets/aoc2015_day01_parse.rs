@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The parser must return an error on malformed input, never panic, and whatever it accepts must
+// be safe to solve
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = aoc2015_day01::parse_reader(data) {
+        assert!(aoc2015_day01::solve(&input).is_ok());
+    }
+});
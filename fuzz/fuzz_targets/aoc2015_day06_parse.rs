@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The parser must return an error on malformed input, never panic, and whatever it accepts must
+// be safe to run on the grid
+fuzz_target!(|data: &[u8]| {
+    if let Ok(instructions) = aoc2015_day06::parse_reader(data) {
+        let mut light_grid = aoc2015_day06::LightGrid::new(aoc2015_day06::Ruleset::Part1);
+        instructions
+            .iter()
+            .for_each(|i| light_grid.run_instruction(i));
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The parser must return an error on malformed input, never panic
+fuzz_target!(|data: &[u8]| {
+    let _ = aoc2015_day07::parse_reader(data);
+});
@@ -0,0 +1,50 @@
+use common::xorshift::XorShift;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Builds a long instruction stream that wanders around the ground floor and only reaches the
+/// basement near the end, so the first basement search can't stop early
+fn generate_input(len: usize) -> Vec<u8> {
+    let mut floor: isize = 0;
+    XorShift::new(0x2015)
+        .take(len)
+        .enumerate()
+        .map(|(i, random)| {
+            let go_up = if i < len * 9 / 10 {
+                floor == 0 || random & 1 == 0
+            } else {
+                random & 3 == 0
+            };
+
+            floor += if go_up { 1 } else { -1 };
+            if go_up {
+                b'('
+            } else {
+                b')'
+            }
+        })
+        .collect()
+}
+
+fn bench_floors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("floors");
+    for len in [1 << 16, 1 << 24] {
+        let input = generate_input(len);
+        let text = std::str::from_utf8(&input).unwrap();
+        assert_eq!(
+            aoc2015_day01::solve(text).unwrap(),
+            aoc2015_day01::solve_parallel(&input).unwrap()
+        );
+
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("scalar", len), text, |b, text| {
+            b.iter(|| aoc2015_day01::solve(text).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", len), &input, |b, input| {
+            b.iter(|| aoc2015_day01::solve_parallel(input).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_floors);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+use serde::Serialize;
+
+use crate::InvalidCharacter;
+
+/// The instructions read as a bracket sequence, with 1-based positions like in the puzzle
+#[derive(Serialize, Debug)]
+pub struct BracketAnalysis {
+    pub max_depth: usize,
+    /// Positions of every matching '(' and ')', ordered by the ')'
+    pub pairs: Vec<(usize, usize)>,
+    pub unmatched_open: Vec<usize>,
+    pub unmatched_close: Vec<usize>,
+    /// First and last position of the longest balanced substring, if there's any
+    pub longest_balanced: Option<(usize, usize)>,
+}
+
+impl BracketAnalysis {
+    pub fn new(input: &[u8]) -> Result<Self, InvalidCharacter> {
+        let mut pairs = Vec::new();
+        let mut unmatched_close = Vec::new();
+        let mut open = Vec::new();
+        let mut max_depth = 0;
+
+        // Start of the balanced substring that ends at the current position, which is right after
+        // the last unmatched ')' or, when inside an unmatched '(', right after it
+        let mut balanced_start = 0;
+        let mut longest_balanced: Option<(usize, usize)> = None;
+
+        for (i, &c) in input.iter().enumerate() {
+            match c {
+                b'(' => {
+                    open.push(i);
+                    max_depth = max_depth.max(open.len());
+                }
+                b')' => match open.pop() {
+                    Some(j) => {
+                        pairs.push((j + 1, i + 1));
+
+                        let start = open.last().map_or(balanced_start, |&k| k + 1);
+                        let is_longer = longest_balanced
+                            .is_none_or(|(first, last)| i + 1 - start > last - first + 1);
+                        if is_longer {
+                            longest_balanced = Some((start + 1, i + 1));
+                        }
+                    }
+                    None => {
+                        unmatched_close.push(i + 1);
+                        balanced_start = i + 1;
+                    }
+                },
+                _ => return Err(InvalidCharacter::from_byte(c, i)),
+            }
+        }
+
+        Ok(BracketAnalysis {
+            max_depth,
+            pairs,
+            unmatched_open: open.into_iter().map(|i| i + 1).collect(),
+            unmatched_close,
+            longest_balanced,
+        })
+    }
+}
@@ -0,0 +1,177 @@
+use std::error::Error;
+use std::fmt;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// What the generated instructions do with the basement
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Basement {
+    Any,
+    Never,
+    /// First reach the basement at this 1-based position
+    FirstAt(usize),
+}
+
+/// Constraints the generated instructions must satisfy
+#[derive(Clone, Copy, Debug)]
+pub struct Constraints {
+    pub length: usize,
+    pub end_floor: isize,
+    pub basement: Basement,
+    /// Lowest and highest floor Santa may be on, unbounded if `None`
+    pub floor_range: Option<(isize, isize)>,
+}
+
+#[derive(Debug)]
+pub struct InfeasibleConstraints {
+    pub reason: &'static str,
+}
+
+impl fmt::Display for InfeasibleConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "No instructions satisfy the constraints: {}",
+            self.reason
+        )
+    }
+}
+
+impl Error for InfeasibleConstraints {}
+
+/// Whether a walk of `steps` instructions can get from floor `from` to floor `to` without leaving
+/// the floors between `lowest` and `highest`
+fn can_reach(from: isize, to: isize, steps: usize, lowest: isize, highest: isize) -> bool {
+    let distance = from.abs_diff(to);
+
+    (lowest..=highest).contains(&from)
+        && (lowest..=highest).contains(&to)
+        && distance <= steps
+        && (steps - distance).is_multiple_of(2)
+        // With room for a single floor, Santa can't move at all
+        && (steps == 0 || lowest < highest)
+}
+
+/// Appends a random walk, choosing each instruction at random among the ones that can still
+/// satisfy the constraints
+fn walk(
+    rng: &mut ChaCha8Rng,
+    instructions: &mut String,
+    from: isize,
+    to: isize,
+    steps: usize,
+    lowest: isize,
+    highest: isize,
+) {
+    let mut floor = from;
+    for left in (0..steps).rev() {
+        let can_go_up = can_reach(floor + 1, to, left, lowest, highest);
+        let can_go_down = can_reach(floor - 1, to, left, lowest, highest);
+
+        let go_up = match (can_go_up, can_go_down) {
+            (true, true) => rng.random_bool(0.5),
+            (can_go_up, _) => can_go_up,
+        };
+
+        if go_up {
+            floor += 1;
+            instructions.push('(');
+        } else {
+            floor -= 1;
+            instructions.push(')');
+        }
+    }
+}
+
+/// Generates random instructions satisfying the constraints, always the same ones for a given
+/// seed
+///
+/// Every instruction is picked at random among the ones that still allow satisfying the
+/// constraints, which is reproducible but not uniform over every valid instruction string.
+pub fn generate(constraints: &Constraints, seed: u64) -> Result<String, InfeasibleConstraints> {
+    let length = constraints.length;
+    let end_floor = constraints.end_floor;
+
+    // No walk can get further than its length, so that bounds the floors too
+    let (lowest, highest) = constraints
+        .floor_range
+        .unwrap_or((-(length as isize), length as isize));
+    if lowest > 0 || highest < 0 {
+        return Err(InfeasibleConstraints {
+            reason: "the floor range must include the ground floor",
+        });
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut instructions = String::with_capacity(length);
+    match constraints.basement {
+        Basement::Any => {
+            if !can_reach(0, end_floor, length, lowest, highest) {
+                return Err(InfeasibleConstraints {
+                    reason: "the end floor can't be reached",
+                });
+            }
+
+            walk(
+                &mut rng,
+                &mut instructions,
+                0,
+                end_floor,
+                length,
+                lowest,
+                highest,
+            );
+        }
+        Basement::Never => {
+            if !can_reach(0, end_floor, length, 0, highest) {
+                return Err(InfeasibleConstraints {
+                    reason: "the end floor can't be reached without entering the basement",
+                });
+            }
+
+            walk(
+                &mut rng,
+                &mut instructions,
+                0,
+                end_floor,
+                length,
+                0,
+                highest,
+            );
+        }
+        Basement::FirstAt(position) => {
+            if position == 0 || position > length || lowest > -1 {
+                return Err(InfeasibleConstraints {
+                    reason: "the basement can't be reached at that position",
+                });
+            }
+
+            // Stay above ground until right before the position, then step down into the basement
+            if !can_reach(0, 0, position - 1, 0, highest) {
+                return Err(InfeasibleConstraints {
+                    reason: "the basement can't be reached at that position",
+                });
+            }
+            if !can_reach(-1, end_floor, length - position, lowest, highest) {
+                return Err(InfeasibleConstraints {
+                    reason: "the end floor can't be reached after entering the basement",
+                });
+            }
+
+            walk(&mut rng, &mut instructions, 0, 0, position - 1, 0, highest);
+            instructions.push(')');
+            walk(
+                &mut rng,
+                &mut instructions,
+                -1,
+                end_floor,
+                length - position,
+                lowest,
+                highest,
+            );
+        }
+    }
+
+    Ok(instructions)
+}
@@ -0,0 +1,79 @@
+use crate::InvalidCharacter;
+
+/// When, and how often, Santa is on a given floor
+///
+/// Positions are 1-based like in the puzzle, and the start on the ground floor counts as a visit
+/// at position 0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FloorVisits {
+    pub first: usize,
+    pub last: usize,
+    pub count: usize,
+}
+
+/// Index of the visits to every floor, built in one go so any floor can be looked up afterwards
+pub struct FloorIndex {
+    lowest: isize,
+    visits: Vec<FloorVisits>,
+}
+
+impl FloorIndex {
+    pub fn new(input: &[u8]) -> Result<Self, InvalidCharacter> {
+        // Floors only ever change by one, so every floor between the lowest and the highest gets
+        // visited, and can be stored contiguously
+        let mut floor: isize = 0;
+        let mut lowest = 0;
+        let mut highest = 0;
+        for (offset, &c) in input.iter().enumerate() {
+            match c {
+                b'(' => floor += 1,
+                b')' => floor -= 1,
+                _ => return Err(InvalidCharacter::from_byte(c, offset)),
+            }
+
+            lowest = lowest.min(floor);
+            highest = highest.max(floor);
+        }
+
+        let mut visits = vec![
+            FloorVisits {
+                first: 0,
+                last: 0,
+                count: 0,
+            };
+            (highest - lowest) as usize + 1
+        ];
+
+        let mut floor = 0;
+        visits[(floor - lowest) as usize].count = 1;
+        for (i, &c) in input.iter().enumerate() {
+            floor += if c == b'(' { 1 } else { -1 };
+
+            let floor_visits = &mut visits[(floor - lowest) as usize];
+            if floor_visits.count == 0 {
+                floor_visits.first = i + 1;
+            }
+            floor_visits.last = i + 1;
+            floor_visits.count += 1;
+        }
+
+        Ok(FloorIndex { lowest, visits })
+    }
+
+    /// Visits to the given floor, or `None` if Santa never gets there
+    pub fn query(&self, floor: isize) -> Option<FloorVisits> {
+        let i = usize::try_from(floor.checked_sub(self.lowest)?).ok()?;
+
+        self.visits.get(i).copied()
+    }
+
+    /// Lowest floor Santa gets to
+    pub fn lowest(&self) -> isize {
+        self.lowest
+    }
+
+    /// Highest floor Santa gets to
+    pub fn highest(&self) -> isize {
+        self.lowest + self.visits.len() as isize - 1
+    }
+}
@@ -0,0 +1,327 @@
+mod analysis;
+mod generator;
+mod index;
+mod parallel;
+mod repair;
+mod tracker;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use std::time::Instant;
+
+use common::check::Problem;
+use common::{Answer, Report};
+
+pub use analysis::BracketAnalysis;
+pub use generator::{generate, Basement, Constraints, InfeasibleConstraints};
+pub use index::{FloorIndex, FloorVisits};
+pub use parallel::solve_parallel;
+pub use repair::{repair, Edit, EditKind, Repair};
+pub use tracker::FloorTracker;
+
+/// How the parser treats characters that aren't parentheses
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseMode {
+    /// Fail on the first such character
+    Strict,
+    /// Skip them, counting them in the warnings
+    Lenient,
+}
+
+/// A character that isn't a parenthesis, found in strict mode
+#[derive(Debug)]
+pub struct InvalidCharacter {
+    pub character: char,
+    /// Byte offset of the character in the input
+    pub offset: usize,
+}
+
+impl InvalidCharacter {
+    /// Builds the error for a single input byte, which may be part of a multi-byte character
+    pub(crate) fn from_byte(byte: u8, offset: usize) -> Self {
+        let character = if byte.is_ascii() {
+            char::from(byte)
+        } else {
+            char::REPLACEMENT_CHARACTER
+        };
+
+        InvalidCharacter { character, offset }
+    }
+
+    /// The same error as a problem at a line and column, like `check_input` reports it
+    ///
+    /// `input` must be the text the error was found in.
+    pub fn to_problem(&self, input: &str) -> Problem {
+        let before = &input.as_bytes()[..self.offset.min(input.len())];
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let column = String::from_utf8_lossy(&before[line_start..])
+            .chars()
+            .count()
+            + 1;
+
+        Problem::new(
+            line,
+            column,
+            format!("Invalid character {:?}", self.character),
+        )
+    }
+}
+
+impl fmt::Display for InvalidCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid character {:?} at offset {}",
+            self.character, self.offset
+        )
+    }
+}
+
+impl Error for InvalidCharacter {}
+
+impl From<InvalidCharacter> for io::Error {
+    fn from(error: InvalidCharacter) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// Characters skipped in lenient mode
+#[derive(Default, Debug)]
+pub struct ParseWarnings {
+    pub whitespace: usize,
+    pub unknown: BTreeMap<char, usize>,
+}
+
+impl ParseWarnings {
+    pub fn is_empty(&self) -> bool {
+        self.whitespace == 0 && self.unknown.is_empty()
+    }
+}
+
+impl fmt::Display for ParseWarnings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "skipped {} whitespace", self.whitespace)?;
+        for (c, count) in &self.unknown {
+            write!(f, ", {} x {:?}", count, c)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<String> {
+    parse_input_with_mode(file_path, ParseMode::Strict).map(|(input, _)| input)
+}
+
+pub fn parse_input_with_mode<T: AsRef<Path>>(
+    file_path: T,
+    mode: ParseMode,
+) -> io::Result<(String, ParseWarnings)> {
+    // Open input file
+    let input = File::open(file_path)?;
+
+    parse_reader_with_mode(input, mode)
+}
+
+pub fn parse_reader<R: Read>(reader: R) -> io::Result<String> {
+    parse_reader_with_mode(reader, ParseMode::Strict).map(|(input, _)| input)
+}
+
+/// Reads the instructions, keeping only the parentheses
+///
+/// In strict mode, any other character fails with an `InvalidCharacter` error. Invalid UTF-8 is
+/// treated as an unknown character in both modes.
+pub fn parse_reader_with_mode<R: Read>(
+    mut reader: R,
+    mode: ParseMode,
+) -> io::Result<(String, ParseWarnings)> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    // Offsets stay exact up to the first invalid sequence, which is all strict mode needs
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut input = String::with_capacity(text.len());
+    let mut warnings = ParseWarnings::default();
+    for (offset, c) in text.char_indices() {
+        match c {
+            '(' | ')' => input.push(c),
+            _ if mode == ParseMode::Strict => {
+                return Err(InvalidCharacter {
+                    character: c,
+                    offset,
+                }
+                .into())
+            }
+            c if c.is_whitespace() => warnings.whitespace += 1,
+            c => *warnings.unknown.entry(c).or_insert(0) += 1,
+        }
+    }
+
+    Ok((input, warnings))
+}
+
+/// Lazily reads the instructions one at a time, so inputs of any size take constant memory
+pub fn parse_stream<R: Read>(reader: R) -> impl Iterator<Item = io::Result<char>> {
+    BufReader::new(reader)
+        .bytes()
+        .enumerate()
+        .map(|(offset, byte)| match byte? {
+            b'(' => Ok('('),
+            b')' => Ok(')'),
+            byte => Err(InvalidCharacter::from_byte(byte, offset).into()),
+        })
+}
+
+/// Reports every character that isn't a parenthesis, without solving
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let mut line = 1;
+    let mut column = 1;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' | ')' => (),
+            '\r' if chars.peek() == Some(&'\n') => {
+                problems.push(Problem::new(line, column, "CRLF line ending"))
+            }
+            '\n' if chars.peek().is_none() => {
+                problems.push(Problem::new(line, column, "Trailing newline"))
+            }
+            '\n' => problems.push(Problem::new(line, column, "Unexpected line break")),
+            c if c.is_whitespace() => {
+                problems.push(Problem::new(line, column, "Unexpected whitespace"))
+            }
+            c => problems.push(Problem::new(
+                line,
+                column,
+                format!("Invalid character {:?}", c),
+            )),
+        }
+
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+
+    if input.is_empty() {
+        problems.push(Problem::new(1, 1, "Empty input"));
+    }
+
+    problems
+}
+
+/// Returns the final floor and the position of the first basement visit, failing on the first
+/// character that isn't a parenthesis
+pub fn solve(input: &str) -> Result<(isize, usize), InvalidCharacter> {
+    let mut floor_counter = 0;
+    let mut first_basement_visit_pos = 0;
+    // Every character before an invalid one is a single byte, so offsets are positions
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => floor_counter += 1,
+            ')' => floor_counter -= 1,
+            _ => {
+                return Err(InvalidCharacter {
+                    character: c,
+                    offset: i,
+                })
+            }
+        }
+
+        if floor_counter == -1 && first_basement_visit_pos == 0 {
+            first_basement_visit_pos = i + 1;
+        }
+    }
+
+    Ok((floor_counter, first_basement_visit_pos))
+}
+
+/// Same as `solve`, but over a stream of instructions, stopping at the first error
+pub fn solve_stream<I: IntoIterator<Item = io::Result<char>>>(
+    instructions: I,
+) -> io::Result<(isize, usize)> {
+    let mut floor_counter = 0;
+    let mut first_basement_visit_pos = 0;
+    for (i, c) in instructions.into_iter().enumerate() {
+        match c? {
+            '(' => floor_counter += 1,
+            ')' => floor_counter -= 1,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Invalid character in input",
+                ))
+            }
+        }
+
+        if floor_counter == -1 && first_basement_visit_pos == 0 {
+            first_basement_visit_pos = i + 1;
+        }
+    }
+
+    Ok((floor_counter, first_basement_visit_pos))
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    run_with_mode(file_path, ParseMode::Strict)
+}
+
+pub fn run_with_mode<T: AsRef<Path>>(
+    file_path: T,
+    mode: ParseMode,
+) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let (input, warnings) = parse_input_with_mode(file_path, mode)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (floor_counter, first_basement_visit_pos) = solve(&input)?;
+    let solution_time = t1.elapsed();
+
+    let mut answers = vec![
+        Answer::new("Floor counter", floor_counter),
+        Answer::new(
+            "Visited basement for the first time at position",
+            first_basement_visit_pos,
+        ),
+    ];
+    if !warnings.is_empty() {
+        answers.push(Answer::new("Warnings", warnings));
+    }
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_reports_invalid_characters() {
+        assert_eq!(solve("(()))(").unwrap(), (0, 5));
+
+        let error = solve("(\n").unwrap_err();
+        assert_eq!((error.character, error.offset), ('\n', 1));
+        let error = solve("()é)").unwrap_err();
+        assert_eq!((error.character, error.offset), ('é', 2));
+    }
+}
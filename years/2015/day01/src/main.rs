@@ -0,0 +1,208 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::{fs, io, process};
+
+use aoc2015_day01::{
+    Basement, BracketAnalysis, Constraints, FloorIndex, InvalidCharacter, ParseMode,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AnalysisFormat {
+    Text,
+    /// Only print the full analysis as JSON, for visualizers
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print random instructions satisfying the given constraints, instead of solving
+    Generate {
+        #[arg(long)]
+        length: usize,
+
+        #[arg(long, allow_negative_numbers = true)]
+        end_floor: isize,
+
+        /// Position at which Santa first enters the basement
+        #[arg(long, conflicts_with = "never_basement")]
+        first_basement: Option<usize>,
+
+        /// Never enter the basement
+        #[arg(long)]
+        never_basement: bool,
+
+        #[arg(long, allow_negative_numbers = true)]
+        min_floor: Option<isize>,
+
+        #[arg(long)]
+        max_floor: Option<isize>,
+
+        /// The same seed always generates the same instructions
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Input file
+    #[arg(long, default_value = "years/2015/day01/input")]
+    input: PathBuf,
+
+    /// Skip whitespace and unknown characters instead of failing on them
+    #[arg(long)]
+    lenient: bool,
+
+    /// Also report when Santa first and last reaches this floor, and how often he's on it
+    #[arg(long, allow_negative_numbers = true)]
+    floor: Vec<isize>,
+
+    /// Also print the fewest flips and deletions that keep Santa out of the basement
+    #[arg(long)]
+    repair: bool,
+
+    /// Floor the repaired instructions must end on
+    #[arg(long, requires = "repair")]
+    end_floor: Option<usize>,
+
+    /// Also analyse the instructions as a bracket sequence
+    #[arg(long, value_enum)]
+    analyze: Option<AnalysisFormat>,
+}
+
+/// Prints an invalid character at its line and column, like `aoc check` does, and exits
+fn exit_on_invalid_character(error: &(dyn Error + 'static), input: &Path) {
+    let Some(invalid) = error
+        .downcast_ref::<io::Error>()
+        .and_then(io::Error::get_ref)
+        .and_then(|inner| inner.downcast_ref::<InvalidCharacter>())
+    else {
+        return;
+    };
+
+    let text = fs::read(input)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    eprintln!("{}:{}", input.display(), invalid.to_problem(&text));
+    process::exit(1);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    if let Some(Command::Generate {
+        length,
+        end_floor,
+        first_basement,
+        never_basement,
+        min_floor,
+        max_floor,
+        seed,
+    }) = cli.command
+    {
+        let basement = match (first_basement, never_basement) {
+            (Some(position), _) => Basement::FirstAt(position),
+            (None, true) => Basement::Never,
+            (None, false) => Basement::Any,
+        };
+        let floor_range = match (min_floor, max_floor) {
+            (None, None) => None,
+            (min_floor, max_floor) => Some((
+                min_floor.unwrap_or(-(length as isize)),
+                max_floor.unwrap_or(length as isize),
+            )),
+        };
+
+        let constraints = Constraints {
+            length,
+            end_floor,
+            basement,
+            floor_range,
+        };
+        println!("{}", aoc2015_day01::generate(&constraints, seed)?);
+
+        return Ok(());
+    }
+
+    let mode = if cli.lenient {
+        ParseMode::Lenient
+    } else {
+        ParseMode::Strict
+    };
+
+    if cli.analyze == Some(AnalysisFormat::Json) {
+        let (input, _) = aoc2015_day01::parse_input_with_mode(&cli.input, mode)
+            .inspect_err(|e| exit_on_invalid_character(e, &cli.input))?;
+        let analysis = BracketAnalysis::new(input.as_bytes())?;
+        println!("{}", serde_json::to_string(&analysis)?);
+
+        return Ok(());
+    }
+
+    let report = aoc2015_day01::run_with_mode(&cli.input, mode)
+        .inspect_err(|e| exit_on_invalid_character(e.as_ref(), &cli.input))?;
+    report.print();
+
+    if cli.floor.is_empty() && !cli.repair && cli.analyze.is_none() {
+        return Ok(());
+    }
+    let (input, _) = aoc2015_day01::parse_input_with_mode(&cli.input, mode)?;
+
+    if !cli.floor.is_empty() {
+        let index = FloorIndex::new(input.as_bytes())?;
+
+        for floor in cli.floor {
+            match index.query(floor) {
+                Some(visits) => println!(
+                    "Floor {}: first reached at position {}, last at position {}, visited {} times",
+                    floor, visits.first, visits.last, visits.count
+                ),
+                None => println!("Floor {}: never reached", floor),
+            }
+        }
+    }
+
+    if cli.analyze.is_some() {
+        let analysis = BracketAnalysis::new(input.as_bytes())?;
+
+        let longest_balanced = match analysis.longest_balanced {
+            Some((first, last)) => format!(
+                "{} instructions, from {} to {}",
+                last - first + 1,
+                first,
+                last
+            ),
+            None => "none".to_string(),
+        };
+        println!(
+            "\nBracket analysis:\nMaximum nesting depth: {}\nMatched pairs: {}\nUnmatched '(': {}\nUnmatched ')': {}\nLongest balanced substring: {}",
+            analysis.max_depth,
+            analysis.pairs.len(),
+            analysis.unmatched_open.len(),
+            analysis.unmatched_close.len(),
+            longest_balanced
+        );
+    }
+
+    if cli.repair {
+        let repair = aoc2015_day01::repair(input.as_bytes(), cli.end_floor)?.ok_or(format!(
+            "Floor {} can't be reached with {} instructions",
+            cli.end_floor.unwrap_or_default(),
+            input.len()
+        ))?;
+
+        let edits: Vec<_> = repair.edits.iter().map(|e| e.to_string()).collect();
+        println!(
+            "\nRepair:\nEdits needed: {}\nEdits: {}\nRepaired instructions: {}",
+            repair.edits.len(),
+            edits.join(", "),
+            repair.instructions
+        );
+    }
+
+    Ok(())
+}
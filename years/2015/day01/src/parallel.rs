@@ -0,0 +1,269 @@
+use rayon::prelude::*;
+
+use crate::InvalidCharacter;
+
+/// Bytes handled by a single task, a multiple of the block size
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Bytes classified at once with SIMD
+const BLOCK_SIZE: usize = 64;
+
+/// Bitmasks of the opening and closing parentheses in a block, with bit `i` set for byte `i`
+#[derive(Clone, Copy)]
+struct BlockMasks {
+    open: u64,
+    close: u64,
+}
+
+impl BlockMasks {
+    #[cfg(target_arch = "x86_64")]
+    fn new(block: &[u8]) -> Self {
+        use std::arch::x86_64::{
+            __m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_set1_epi8,
+        };
+
+        if block.len() < BLOCK_SIZE {
+            return Self::new_scalar(block);
+        }
+
+        let mut open = 0;
+        let mut close = 0;
+        // SAFETY: SSE2 is always available on x86_64, and every load reads 16 bytes inside the
+        // 64 byte block
+        unsafe {
+            let open_paren = _mm_set1_epi8(b'(' as i8);
+            let close_paren = _mm_set1_epi8(b')' as i8);
+            for lane in 0..BLOCK_SIZE / 16 {
+                let bytes = _mm_loadu_si128(block.as_ptr().add(lane * 16) as *const __m128i);
+                let open_lane = _mm_movemask_epi8(_mm_cmpeq_epi8(bytes, open_paren)) as u16;
+                let close_lane = _mm_movemask_epi8(_mm_cmpeq_epi8(bytes, close_paren)) as u16;
+                open |= (open_lane as u64) << (lane * 16);
+                close |= (close_lane as u64) << (lane * 16);
+            }
+        }
+
+        BlockMasks { open, close }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    fn new(block: &[u8]) -> Self {
+        Self::new_scalar(block)
+    }
+
+    fn new_scalar(block: &[u8]) -> Self {
+        let mut masks = BlockMasks { open: 0, close: 0 };
+        for (i, &c) in block.iter().enumerate() {
+            match c {
+                b'(' => masks.open |= 1 << i,
+                b')' => masks.close |= 1 << i,
+                _ => (),
+            }
+        }
+
+        masks
+    }
+
+    /// Index of the first byte in the block that isn't a parenthesis
+    fn first_invalid(&self, len: usize) -> Option<usize> {
+        let valid = self.open | self.close;
+        let expected = if len == BLOCK_SIZE {
+            u64::MAX
+        } else {
+            (1 << len) - 1
+        };
+
+        (valid != expected).then(|| (!valid).trailing_zeros() as usize)
+    }
+
+    fn delta(&self) -> isize {
+        self.open.count_ones() as isize - self.close.count_ones() as isize
+    }
+}
+
+/// Net floor change over a chunk, and the lowest floor reached inside it, both relative to the
+/// floor the chunk starts on
+///
+/// Summaries combine associatively, which is what lets the chunks be processed independently.
+#[derive(Clone, Copy)]
+struct ChunkSummary {
+    delta: isize,
+    min_prefix: isize,
+}
+
+impl ChunkSummary {
+    fn of(chunk: &[u8], chunk_offset: usize) -> Result<Self, InvalidCharacter> {
+        let mut floor = 0;
+        let mut min_prefix = isize::MAX;
+        for (i, block) in chunk.chunks(BLOCK_SIZE).enumerate() {
+            let masks = BlockMasks::new(block);
+            if let Some(j) = masks.first_invalid(block.len()) {
+                let offset = i * BLOCK_SIZE + j;
+                return Err(InvalidCharacter::from_byte(
+                    chunk[offset],
+                    chunk_offset + offset,
+                ));
+            }
+
+            // A block can only lower the minimum if going all the way down would get below it
+            if floor - (block.len() as isize) < min_prefix {
+                min_prefix = min_prefix.min(floor + lowest_in_block(masks, block.len()).0);
+            }
+            floor += masks.delta();
+        }
+
+        Ok(ChunkSummary {
+            delta: floor,
+            min_prefix,
+        })
+    }
+}
+
+/// Lowest floor reached in a block relative to its start, and the index it's first reached at
+fn lowest_in_block(masks: BlockMasks, len: usize) -> (isize, usize) {
+    let mut floor = 0;
+    let mut lowest = (isize::MAX, 0);
+    for i in 0..len {
+        if masks.open & (1 << i) != 0 {
+            floor += 1;
+        } else {
+            floor -= 1;
+        }
+
+        if floor < lowest.0 {
+            lowest = (floor, i);
+        }
+    }
+
+    lowest
+}
+
+/// Same as `solve`, but splits the input in chunks that are summarised in parallel, classifying
+/// 64 bytes at a time with SIMD
+///
+/// A prefix scan over the chunk summaries gives the floor each chunk starts on, and only the chunk
+/// where the basement is first reached gets scanned again to find the exact position.
+pub fn solve_parallel(input: &[u8]) -> Result<(isize, usize), InvalidCharacter> {
+    let summaries = input
+        .par_chunks(CHUNK_SIZE)
+        .enumerate()
+        .map(|(i, chunk)| ChunkSummary::of(chunk, i * CHUNK_SIZE))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut floor_counter = 0;
+    let mut first_basement_visit_pos = 0;
+    for (i, summary) in summaries.iter().enumerate() {
+        if first_basement_visit_pos == 0 && floor_counter + summary.min_prefix <= -1 {
+            let chunk_offset = i * CHUNK_SIZE;
+            let chunk = &input[chunk_offset..(chunk_offset + CHUNK_SIZE).min(input.len())];
+            first_basement_visit_pos = chunk_offset + first_below(chunk, floor_counter) + 1;
+        }
+
+        floor_counter += summary.delta;
+    }
+
+    Ok((floor_counter, first_basement_visit_pos))
+}
+
+/// Index of the first instruction that takes Santa below the ground floor, starting on `floor`
+///
+/// The chunk must have been validated, and must actually reach the basement.
+fn first_below(chunk: &[u8], mut floor: isize) -> usize {
+    for (i, block) in chunk.chunks(BLOCK_SIZE).enumerate() {
+        let masks = BlockMasks::new(block);
+        if floor - (block.len() as isize) < 0 {
+            let (lowest, j) = lowest_in_block(masks, block.len());
+            if floor + lowest < 0 {
+                // The lowest point is below ground, but the basement may have been reached earlier
+                let mut block_floor = floor;
+                for (k, &c) in block[..=j].iter().enumerate() {
+                    block_floor += if c == b'(' { 1 } else { -1 };
+                    if block_floor < 0 {
+                        return i * BLOCK_SIZE + k;
+                    }
+                }
+            }
+        }
+        floor += masks.delta();
+    }
+
+    unreachable!("Chunk never reaches the basement")
+}
+
+#[cfg(test)]
+mod tests {
+    use common::xorshift::XorShift;
+
+    use super::*;
+
+    fn random_instructions(len: usize, seed: u64) -> Vec<u8> {
+        XorShift::new(seed)
+            .take(len)
+            .map(|random| if random & 1 == 0 { b'(' } else { b')' })
+            .collect()
+    }
+
+    /// Going up and down until `position`, 1-based and odd, where Santa first enters the
+    /// basement, then up for the rest
+    fn basement_at(position: usize, len: usize) -> Vec<u8> {
+        let mut input = b"()".repeat((position - 1) / 2);
+        input.push(b')');
+        input.resize(len, b'(');
+        input
+    }
+
+    fn assert_same_as_solve(input: &[u8]) {
+        let text = std::str::from_utf8(input).unwrap();
+        assert_eq!(solve_parallel(input).unwrap(), crate::solve(text).unwrap());
+    }
+
+    #[test]
+    fn matches_solve_around_chunk_sizes() {
+        for len in [
+            0,
+            1,
+            BLOCK_SIZE - 1,
+            BLOCK_SIZE + 1,
+            CHUNK_SIZE - 1,
+            CHUNK_SIZE,
+            CHUNK_SIZE + 1,
+            3 * CHUNK_SIZE + 17,
+        ] {
+            assert_same_as_solve(&random_instructions(len, len as u64));
+        }
+    }
+
+    #[test]
+    fn finds_basement_at_chunk_edges() {
+        let len = 2 * CHUNK_SIZE + BLOCK_SIZE;
+        for position in [
+            1,
+            BLOCK_SIZE + 1,
+            CHUNK_SIZE - 1,
+            CHUNK_SIZE + 1,
+            2 * CHUNK_SIZE - 1,
+            2 * CHUNK_SIZE + 1,
+        ] {
+            let input = basement_at(position, len);
+            assert_eq!(solve_parallel(&input).unwrap().1, position);
+            assert_same_as_solve(&input);
+        }
+    }
+
+    #[test]
+    fn never_reaching_basement() {
+        let input = b"(".repeat(CHUNK_SIZE + 1);
+        assert_eq!(
+            solve_parallel(&input).unwrap(),
+            (CHUNK_SIZE as isize + 1, 0)
+        );
+    }
+
+    #[test]
+    fn reports_invalid_character_offset_in_later_chunk() {
+        let mut input = random_instructions(2 * CHUNK_SIZE, 1);
+        input[CHUNK_SIZE + 5] = b'x';
+
+        let error = solve_parallel(&input).unwrap_err();
+        assert_eq!((error.character, error.offset), ('x', CHUNK_SIZE + 5));
+    }
+}
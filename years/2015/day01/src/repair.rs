@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::InvalidCharacter;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditKind {
+    /// Turn a parenthesis into the opposite one
+    Flip,
+    Delete,
+}
+
+/// An edit to the instruction at a 1-based position of the original input
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Edit {
+    pub position: usize,
+    pub kind: EditKind,
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            EditKind::Flip => write!(f, "flip at {}", self.position),
+            EditKind::Delete => write!(f, "delete at {}", self.position),
+        }
+    }
+}
+
+/// Instructions that keep Santa out of the basement, and the edits that produced them
+pub struct Repair {
+    pub instructions: String,
+    pub edits: Vec<Edit>,
+}
+
+/// Finds the fewest flips and deletions that keep Santa from ever going below the ground floor,
+/// optionally also making him end up on `end_floor`
+///
+/// Returns `None` if `end_floor` is higher than the number of instructions, which makes it
+/// unreachable.
+pub fn repair(input: &[u8], end_floor: Option<usize>) -> Result<Option<Repair>, InvalidCharacter> {
+    if let Some((offset, &c)) = input
+        .iter()
+        .enumerate()
+        .find(|(_, &c)| c != b'(' && c != b')')
+    {
+        return Err(InvalidCharacter::from_byte(c, offset));
+    }
+
+    match end_floor {
+        None => Ok(Some(repair_greedy(input))),
+        Some(end_floor) => Ok(repair_to_floor(input, end_floor)),
+    }
+}
+
+/// Without a floor to end on, flipping every ')' that would take Santa below ground is optimal
+///
+/// A flip and a deletion both fix the current instruction, but the flip leaves Santa a floor
+/// higher, which can only help with the instructions that follow.
+fn repair_greedy(input: &[u8]) -> Repair {
+    let mut instructions = String::with_capacity(input.len());
+    let mut edits = Vec::new();
+
+    let mut floor = 0;
+    for (i, &c) in input.iter().enumerate() {
+        if c == b'(' {
+            floor += 1;
+            instructions.push('(');
+        } else if floor > 0 {
+            floor -= 1;
+            instructions.push(')');
+        } else {
+            floor += 1;
+            instructions.push('(');
+            edits.push(Edit {
+                position: i + 1,
+                kind: EditKind::Flip,
+            });
+        }
+    }
+
+    Repair {
+        instructions,
+        edits,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Choice {
+    Keep,
+    Flip,
+    Delete,
+}
+
+/// Dynamic programming over (instruction, floor), which takes quadratic time and memory
+///
+/// Floors are capped at what can still get down to `end_floor` with the instructions left, which
+/// halves the states in the worst case.
+fn repair_to_floor(input: &[u8], end_floor: usize) -> Option<Repair> {
+    let n = input.len();
+    if end_floor > n {
+        return None;
+    }
+
+    const UNREACHABLE: usize = usize::MAX;
+
+    // Fewest edits to be on each floor after the instructions processed so far, and the choice
+    // that led to each floor after every instruction
+    let mut edits_to = vec![0];
+    let mut choices: Vec<Vec<Choice>> = Vec::with_capacity(n);
+
+    for (i, &c) in input.iter().enumerate() {
+        let step: isize = if c == b'(' { 1 } else { -1 };
+        let max_floor = (i + 1).min(end_floor + (n - i - 1));
+
+        let mut next = vec![UNREACHABLE; max_floor + 1];
+        let mut next_choices = vec![Choice::Keep; max_floor + 1];
+        for (floor, &edits) in edits_to.iter().enumerate() {
+            if edits == UNREACHABLE {
+                continue;
+            }
+
+            for (choice, to, cost) in [
+                (Choice::Keep, floor as isize + step, 0),
+                (Choice::Flip, floor as isize - step, 1),
+                (Choice::Delete, floor as isize, 1),
+            ] {
+                if to < 0 || to as usize > max_floor {
+                    continue;
+                }
+
+                let to = to as usize;
+                if edits + cost < next[to] {
+                    next[to] = edits + cost;
+                    next_choices[to] = choice;
+                }
+            }
+        }
+
+        edits_to = next;
+        choices.push(next_choices);
+    }
+
+    // Walk the choices back from the end floor
+    let mut floor = end_floor;
+    let mut kept = Vec::with_capacity(n);
+    let mut edits = Vec::new();
+    for (i, &c) in input.iter().enumerate().rev() {
+        let step: isize = if c == b'(' { 1 } else { -1 };
+        match choices[i][floor] {
+            Choice::Keep => {
+                kept.push(c);
+                floor = (floor as isize - step) as usize;
+            }
+            Choice::Flip => {
+                kept.push(if c == b'(' { b')' } else { b'(' });
+                floor = (floor as isize + step) as usize;
+                edits.push(Edit {
+                    position: i + 1,
+                    kind: EditKind::Flip,
+                });
+            }
+            Choice::Delete => edits.push(Edit {
+                position: i + 1,
+                kind: EditKind::Delete,
+            }),
+        }
+    }
+
+    kept.reverse();
+    edits.reverse();
+
+    Some(Repair {
+        instructions: kept.into_iter().map(char::from).collect(),
+        edits,
+    })
+}
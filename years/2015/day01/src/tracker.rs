@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+use crate::InvalidCharacter;
+
+/// Follows Santa through the building one instruction at a time, collecting statistics on the way
+///
+/// Positions are 1-based like in the puzzle, with position 0 being the start on the ground floor.
+pub struct FloorTracker {
+    position: usize,
+    floor: isize,
+    basement_entries: Vec<usize>,
+    basement_exits: Vec<usize>,
+    min_floor: (isize, usize),
+    max_floor: (isize, usize),
+    steps_below_ground: usize,
+    histogram: BTreeMap<isize, usize>,
+}
+
+impl Default for FloorTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FloorTracker {
+    pub fn new() -> Self {
+        FloorTracker {
+            position: 0,
+            floor: 0,
+            basement_entries: Vec::new(),
+            basement_exits: Vec::new(),
+            min_floor: (0, 0),
+            max_floor: (0, 0),
+            steps_below_ground: 0,
+            histogram: BTreeMap::from([(0, 1)]),
+        }
+    }
+
+    /// Tracks every instruction read until the end of the reader
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut tracker = Self::new();
+
+        let mut buf = [0; 8192];
+        loop {
+            let read = reader.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            tracker.feed(&buf[..read])?;
+        }
+
+        Ok(tracker)
+    }
+
+    /// Tracks a chunk of instructions, which may be the continuation of a previous chunk
+    ///
+    /// Stops at the first byte that isn't a parenthesis, keeping the instructions before it.
+    pub fn feed(&mut self, instructions: &[u8]) -> io::Result<()> {
+        instructions.iter().try_for_each(|&c| self.step(c))
+    }
+
+    /// Tracks a single instruction, failing with an `InvalidCharacter` error if it isn't a
+    /// parenthesis
+    pub fn step(&mut self, instruction: u8) -> io::Result<()> {
+        let previous_floor = self.floor;
+        match instruction {
+            b'(' => self.floor += 1,
+            b')' => self.floor -= 1,
+            _ => return Err(InvalidCharacter::from_byte(instruction, self.position).into()),
+        }
+        self.position += 1;
+
+        if previous_floor == 0 && self.floor == -1 {
+            self.basement_entries.push(self.position);
+        } else if previous_floor == -1 && self.floor == 0 {
+            self.basement_exits.push(self.position);
+        }
+
+        if self.floor < self.min_floor.0 {
+            self.min_floor = (self.floor, self.position);
+        }
+        if self.floor > self.max_floor.0 {
+            self.max_floor = (self.floor, self.position);
+        }
+
+        if self.floor < 0 {
+            self.steps_below_ground += 1;
+        }
+
+        *self.histogram.entry(self.floor).or_insert(0) += 1;
+
+        Ok(())
+    }
+
+    /// Number of instructions tracked so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Floor Santa is currently on
+    pub fn floor(&self) -> isize {
+        self.floor
+    }
+
+    /// Positions at which Santa went from the ground floor down to the basement
+    pub fn basement_entries(&self) -> &[usize] {
+        &self.basement_entries
+    }
+
+    /// Positions at which Santa went from the basement back up to the ground floor
+    pub fn basement_exits(&self) -> &[usize] {
+        &self.basement_exits
+    }
+
+    /// Lowest floor reached, and the position it was first reached at
+    pub fn min_floor(&self) -> (isize, usize) {
+        self.min_floor
+    }
+
+    /// Highest floor reached, and the position it was first reached at
+    pub fn max_floor(&self) -> (isize, usize) {
+        self.max_floor
+    }
+
+    /// Number of instructions after which Santa was below the ground floor
+    pub fn steps_below_ground(&self) -> usize {
+        self.steps_below_ground
+    }
+
+    /// How many times Santa was on each floor, counting the start
+    pub fn histogram(&self) -> &BTreeMap<isize, usize> {
+        &self.histogram
+    }
+}
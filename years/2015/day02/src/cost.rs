@@ -0,0 +1,349 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{ArithmeticOverflow, Length, PackagingRules, Present};
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PaperRoll {
+    pub width: Length,
+    pub length: Length,
+    pub price: f64,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RibbonSpool {
+    pub length: Length,
+    pub price: f64,
+}
+
+/// What the materials come in and cost, e.g. loaded from a JSON file such as
+/// `{"paper_roll": {"width": "30in", "length": "25", "price": 4.5}, "ribbon_spool": {"length": "100", "price": 2}}`
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PriceTable {
+    pub paper_roll: PaperRoll,
+    pub ribbon_spool: RibbonSpool,
+}
+
+impl PriceTable {
+    pub fn load<T: AsRef<Path>>(file_path: T) -> io::Result<Self> {
+        let table = BufReader::new(File::open(file_path)?);
+
+        Ok(serde_json::from_reader(table)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct CostEstimate {
+    pub paper_rolls: usize,
+    pub ribbon_spools: usize,
+    pub total_cost: f64,
+    /// Percentage of the bought paper that isn't used
+    pub paper_waste: f64,
+    /// Percentage of the bought ribbon that isn't used
+    pub ribbon_waste: f64,
+}
+
+#[derive(Debug)]
+pub enum CostError {
+    /// The paper roll or ribbon spool has a zero side, so nothing can be cut from it
+    EmptyMaterial(&'static str),
+    Overflow(ArithmeticOverflow),
+}
+
+impl fmt::Display for CostError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CostError::EmptyMaterial(material) => {
+                write!(
+                    f,
+                    "The {} has a zero size, nothing can be cut from it",
+                    material
+                )
+            }
+            CostError::Overflow(overflow) => write!(f, "{}", overflow),
+        }
+    }
+}
+
+impl Error for CostError {}
+
+impl From<ArithmeticOverflow> for CostError {
+    fn from(overflow: ArithmeticOverflow) -> Self {
+        CostError::Overflow(overflow)
+    }
+}
+
+const PAPER_OVERFLOW: ArithmeticOverflow = ArithmeticOverflow {
+    quantity: "paper to cut",
+};
+const RIBBON_OVERFLOW: ArithmeticOverflow = ArithmeticOverflow {
+    quantity: "ribbon to cut",
+};
+
+/// Estimates how many rolls and spools are needed to cut every ordered present's pieces,
+/// following the rules
+///
+/// Paper pieces are packed on shelves across the roll, tallest first, each into the shelf
+/// that leaves the least room. Pieces wider than the roll either way are cut into strips, and
+/// pieces longer than it into segments, to be taped together. Ribbon pieces are cut longest
+/// first from the spool that leaves the least, with any longer than a spool spliced. Identical
+/// pieces are counted rather than listed, so huge presents or quantities don't take any longer.
+///
+/// Fails on rolls or spools with a zero side, which the price table's loader already rejects.
+pub fn estimate_cost(
+    orders: &[(Present, NonZeroUsize)],
+    prices: &PriceTable,
+    rules: &dyn PackagingRules,
+) -> Result<CostEstimate, CostError> {
+    let roll = prices.paper_roll;
+    let roll_width = roll.width.micrometres();
+    let roll_length = roll.length.micrometres();
+    let spool_length = prices.ribbon_spool.length.micrometres();
+    if roll_width == 0 || roll_length == 0 {
+        return Err(CostError::EmptyMaterial("paper roll"));
+    }
+    if spool_length == 0 {
+        return Err(CostError::EmptyMaterial("ribbon spool"));
+    }
+
+    let mut pieces = Vec::new();
+    let mut ribbons = Vec::new();
+    let mut whole_spools = 0u128;
+    for (present, quantity) in orders {
+        let quantity = quantity.get() as u128;
+        for (a, b) in rules.paper_pieces(present)? {
+            cut_piece(
+                a.micrometres(),
+                b.micrometres(),
+                quantity,
+                roll_width,
+                roll_length,
+                &mut pieces,
+            )?;
+        }
+        for ribbon in rules.ribbon_pieces(present)? {
+            // Pieces longer than a spool take whole spools, spliced to the rest
+            let ribbon = ribbon.micrometres();
+            whole_spools = (ribbon / spool_length)
+                .checked_mul(quantity)
+                .and_then(|spools| whole_spools.checked_add(spools))
+                .ok_or(RIBBON_OVERFLOW)?;
+            if ribbon % spool_length > 0 {
+                ribbons.push((ribbon % spool_length, quantity));
+            }
+        }
+    }
+
+    let paper_used = pieces
+        .iter()
+        .try_fold(0u128, |total, &(across, along, count)| {
+            total.checked_add(across.checked_mul(along)?.checked_mul(count)?)
+        })
+        .ok_or(PAPER_OVERFLOW)?;
+    let paper_rolls = usize::try_from(count_rolls(pieces, roll_width, roll_length)?)
+        .map_err(|_| PAPER_OVERFLOW)?;
+
+    let ribbon_used = ribbons
+        .iter()
+        .try_fold(0u128, |total, &(ribbon, count)| {
+            total.checked_add(ribbon.checked_mul(count)?)
+        })
+        .and_then(|used| used.checked_add(whole_spools.checked_mul(spool_length)?))
+        .ok_or(RIBBON_OVERFLOW)?;
+    let ribbon_spools = whole_spools
+        .checked_add(count_spools(ribbons, spool_length))
+        .and_then(|spools| usize::try_from(spools).ok())
+        .ok_or(RIBBON_OVERFLOW)?;
+
+    let waste = |used: u128, bought: f64| {
+        if bought == 0.0 {
+            0.0
+        } else {
+            100.0 * (1.0 - used as f64 / bought)
+        }
+    };
+
+    Ok(CostEstimate {
+        paper_rolls,
+        ribbon_spools,
+        total_cost: paper_rolls as f64 * roll.price
+            + ribbon_spools as f64 * prices.ribbon_spool.price,
+        paper_waste: waste(
+            paper_used,
+            paper_rolls as f64 * roll_width as f64 * roll_length as f64,
+        ),
+        ribbon_waste: waste(ribbon_used, ribbon_spools as f64 * spool_length as f64),
+    })
+}
+
+/// Splits `count` pieces of `a` by `b` into pieces that fit on the roll, as (across, along, count)
+fn cut_piece(
+    a: u128,
+    b: u128,
+    count: u128,
+    roll_width: u128,
+    roll_length: u128,
+    pieces: &mut Vec<(u128, u128, u128)>,
+) -> Result<(), ArithmeticOverflow> {
+    let (short, long) = (a.min(b), a.max(b));
+    if short == 0 {
+        return Ok(());
+    }
+
+    // Lay pieces across the roll when they fit, to keep the shelves short
+    let mut strips = Vec::with_capacity(2);
+    if long <= roll_width {
+        strips.push((long, short, count));
+    } else if short <= roll_width {
+        strips.push((short, long, count));
+    } else {
+        let full_strips = (short / roll_width)
+            .checked_mul(count)
+            .ok_or(PAPER_OVERFLOW)?;
+        strips.push((roll_width, long, full_strips));
+        if short % roll_width > 0 {
+            strips.push((short % roll_width, long, count));
+        }
+    }
+
+    for (across, along, count) in strips {
+        let segments = along / roll_length;
+        if segments > 0 {
+            let count = count.checked_mul(segments).ok_or(PAPER_OVERFLOW)?;
+            pieces.push((across, roll_length, count));
+        }
+        if along % roll_length > 0 {
+            pieces.push((across, along % roll_length, count));
+        }
+    }
+
+    Ok(())
+}
+
+/// Best fit of `count` items of `size` into bins, the one leaving the least room first, where
+/// `bins` counts the bins left with each room, so identical items are placed together
+///
+/// Bins of `capacity` are opened once no bin has room, and the number opened is returned.
+fn best_fit(bins: &mut BTreeMap<u128, u128>, size: u128, mut count: u128, capacity: u128) -> u128 {
+    let add = |bins: &mut BTreeMap<u128, u128>, room: u128, number: u128| {
+        if number > 0 {
+            *bins.entry(room).or_default() += number;
+        }
+    };
+
+    // Every bin that fits is filled up before trying the next, its room then being too small
+    while count > 0 {
+        let Some((&room, &number)) = bins.range(size..).next() else {
+            break;
+        };
+        bins.remove(&room);
+        let per_bin = room / size;
+
+        match number.checked_mul(per_bin) {
+            Some(fits) if count >= fits => {
+                add(bins, room - per_bin * size, number);
+                count -= fits;
+            }
+            _ => {
+                let filled = count / per_bin;
+                let rest = count % per_bin;
+                add(bins, room - per_bin * size, filled);
+                add(bins, room - rest * size, u128::from(rest > 0));
+                add(bins, room, number - filled - u128::from(rest > 0));
+                count = 0;
+            }
+        }
+    }
+
+    let per_bin = capacity / size;
+    let filled = count / per_bin;
+    let rest = count % per_bin;
+    add(bins, capacity - per_bin * size, filled);
+    add(bins, capacity - rest * size, u128::from(rest > 0));
+
+    filled + u128::from(rest > 0)
+}
+
+/// Best fit decreasing height shelf packing, given pieces that fit on a roll
+fn count_rolls(
+    mut pieces: Vec<(u128, u128, u128)>,
+    roll_width: u128,
+    roll_length: u128,
+) -> Result<u128, ArithmeticOverflow> {
+    pieces.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)));
+
+    // Room left on the shelves and on the rolls, and how many have it
+    let mut shelves = BTreeMap::new();
+    let mut rolls = BTreeMap::new();
+    let mut roll_count = 0u128;
+    for (across, along, count) in pieces {
+        // New shelves are as tall as these pieces since they come tallest first
+        let new_shelves = best_fit(&mut shelves, across, count, roll_width);
+        roll_count = roll_count
+            .checked_add(best_fit(&mut rolls, along, new_shelves, roll_length))
+            .ok_or(PAPER_OVERFLOW)?;
+    }
+
+    Ok(roll_count)
+}
+
+/// Best fit decreasing, given pieces shorter than a spool, with how many of each
+fn count_spools(mut ribbons: Vec<(u128, u128)>, spool_length: u128) -> u128 {
+    ribbons.sort_unstable_by_key(|&(ribbon, _)| Reverse(ribbon));
+
+    // Ribbon left on the started spools, and how many have it
+    let mut spools = BTreeMap::new();
+    ribbons
+        .into_iter()
+        .map(|(ribbon, count)| best_fit(&mut spools, ribbon, count, spool_length))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules2015;
+
+    fn prices(roll_width: usize, roll_length: usize, spool_length: usize) -> PriceTable {
+        PriceTable {
+            paper_roll: PaperRoll {
+                width: Length::feet(roll_width),
+                length: Length::feet(roll_length),
+                price: 4.5,
+            },
+            ribbon_spool: RibbonSpool {
+                length: Length::feet(spool_length),
+                price: 2.0,
+            },
+        }
+    }
+
+    #[test]
+    fn rejects_zero_sized_materials() {
+        let orders = [(Present::new(2, 3, 4), NonZeroUsize::MIN)];
+        for (prices, material) in [
+            (prices(0, 25, 100), "paper roll"),
+            (prices(3, 0, 100), "paper roll"),
+            (prices(3, 25, 0), "ribbon spool"),
+        ] {
+            assert!(matches!(
+                estimate_cost(&orders, &prices, &Rules2015),
+                Err(CostError::EmptyMaterial(empty)) if empty == material
+            ));
+        }
+
+        let estimate = estimate_cost(&orders, &prices(3, 25, 100), &Rules2015).unwrap();
+        assert_eq!((estimate.paper_rolls, estimate.ribbon_spools), (1, 1));
+    }
+}
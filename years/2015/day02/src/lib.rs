@@ -0,0 +1,304 @@
+mod cost;
+mod packing;
+mod parse;
+mod report;
+mod rules;
+mod units;
+
+use std::error::Error;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::Instant;
+
+use common::check::{self, Problem};
+use common::{Answer, Report};
+
+pub use cost::{estimate_cost, CostError, CostEstimate, PaperRoll, PriceTable, RibbonSpool};
+pub use packing::{pack, PackedContainer, Packing, PackingError, Placement, MAX_PRESENTS};
+pub use parse::{
+    parse_input, parse_input_with_options, parse_json, parse_present, parse_reader,
+    parse_reader_with_options, parse_stream, parse_stream_with_options, InputFormat, ParseError,
+    ParseErrorKind, ParseOptions,
+};
+pub use report::{BreakdownReport, PresentBreakdown, Statistics, VolumeBucket};
+pub use rules::{ConfiguredRules, PackagingRules, RibbonRule, Rules2015, WrappingRule};
+pub use units::{Area, ArithmeticOverflow, Length, Unit, UnknownUnit, Volume};
+
+pub(crate) const WRAPPING_OVERFLOW: ArithmeticOverflow = ArithmeticOverflow {
+    quantity: "wrapping paper",
+};
+pub(crate) const RIBBON_OVERFLOW: ArithmeticOverflow = ArithmeticOverflow { quantity: "ribbon" };
+
+#[derive(Clone, Copy, Debug)]
+pub struct Present {
+    length: Length,
+    width: Length,
+    height: Length,
+}
+
+impl Present {
+    /// A present measured in feet
+    pub fn new(length: usize, width: usize, height: usize) -> Self {
+        Present::with_lengths(
+            Length::feet(length),
+            Length::feet(width),
+            Length::feet(height),
+        )
+    }
+
+    pub fn with_lengths(length: Length, width: Length, height: Length) -> Self {
+        Present {
+            length,
+            width,
+            height,
+        }
+    }
+
+    pub fn dimensions(&self) -> [Length; 3] {
+        [self.length, self.width, self.height]
+    }
+
+    /// The pieces of paper the 2015 rules need: every side, and the smallest one again as slack
+    pub fn rectangles(&self) -> [(Length, Length); 7] {
+        let mut dimensions = self.dimensions();
+        dimensions.sort();
+        let [short, middle, long] = dimensions;
+
+        [
+            (short, middle),
+            (short, middle),
+            (short, long),
+            (short, long),
+            (middle, long),
+            (middle, long),
+            (short, middle),
+        ]
+    }
+
+    pub fn surface_area(&self) -> Result<Area, ArithmeticOverflow> {
+        let [length_x_width, length_x_height, width_x_height] = self.side_areas()?;
+
+        length_x_width
+            .checked_add(length_x_height)
+            .and_then(|area| area.checked_add(width_x_height))
+            .and_then(|area| area.checked_mul(2))
+            .ok_or(WRAPPING_OVERFLOW)
+    }
+
+    /// The extra paper needed, the area of the smallest side
+    pub fn slack(&self) -> Result<Area, ArithmeticOverflow> {
+        let [length_x_width, length_x_height, width_x_height] = self.side_areas()?;
+
+        Ok(length_x_width.min(length_x_height).min(width_x_height))
+    }
+
+    pub fn calculate_wrapping_amount(&self) -> Result<Area, ArithmeticOverflow> {
+        self.surface_area()?
+            .checked_add(self.slack()?)
+            .ok_or(WRAPPING_OVERFLOW)
+    }
+
+    /// The smallest perimeter of any face
+    pub fn ribbon_perimeter(&self) -> Result<Length, ArithmeticOverflow> {
+        let mut dimensions = self.dimensions();
+        dimensions.sort();
+
+        dimensions[0]
+            .checked_add(dimensions[1])
+            .and_then(|half| half.checked_add(half))
+            .ok_or(RIBBON_OVERFLOW)
+    }
+
+    pub fn volume(&self) -> Result<Volume, ArithmeticOverflow> {
+        Volume::from_sides(self.length, self.width, self.height).ok_or(RIBBON_OVERFLOW)
+    }
+
+    /// The bow takes as many feet of ribbon as the present has cubic feet of volume,
+    /// truncated to the micrometre when the present isn't measured in feet
+    pub fn bow(&self) -> Result<Length, ArithmeticOverflow> {
+        let foot = Unit::Foot.micrometres();
+
+        Ok(Length::from_micrometres(
+            self.volume()?.cubic_micrometres() / (foot * foot),
+        ))
+    }
+
+    pub fn calculate_ribbon_amount(&self) -> Result<Length, ArithmeticOverflow> {
+        self.ribbon_perimeter()?
+            .checked_add(self.bow()?)
+            .ok_or(RIBBON_OVERFLOW)
+    }
+
+    fn side_areas(&self) -> Result<[Area; 3], ArithmeticOverflow> {
+        let side = |a, b| Area::from_sides(a, b).ok_or(WRAPPING_OVERFLOW);
+
+        Ok([
+            side(self.length, self.width)?,
+            side(self.length, self.height)?,
+            side(self.width, self.height)?,
+        ])
+    }
+}
+
+/// Reports every line that isn't a present in the detected format, without solving
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = check::check_line_endings(input);
+
+    let format = InputFormat::detect(input.as_bytes());
+    if format == InputFormat::Json {
+        if let Err(e) = parse_json(input) {
+            problems.push(Problem::new(e.line, e.column, e.kind.to_string()));
+        }
+
+        return problems;
+    }
+
+    let options = ParseOptions {
+        format,
+        ..Default::default()
+    };
+    for (i, line) in input.lines().enumerate() {
+        if let Err(e) = parse_present(line, i + 1, options) {
+            problems.push(Problem::new(e.line, e.column, e.kind.to_string()));
+        }
+    }
+
+    problems
+}
+
+/// Returns the total amount of wrapping paper and ribbon required, given every present with how
+/// many of it are ordered
+pub fn solve(orders: &[(Present, NonZeroUsize)]) -> Result<(Area, Length), ArithmeticOverflow> {
+    solve_with_rules(orders, &Rules2015)
+}
+
+/// Same as `solve`, following other packaging rules
+pub fn solve_with_rules(
+    orders: &[(Present, NonZeroUsize)],
+    rules: &dyn PackagingRules,
+) -> Result<(Area, Length), ArithmeticOverflow> {
+    let mut total_wrapping_required = Area::default();
+    let mut total_ribbon_required = Length::default();
+    for (present, quantity) in orders {
+        (total_wrapping_required, total_ribbon_required) = add_order(
+            present,
+            *quantity,
+            rules,
+            total_wrapping_required,
+            total_ribbon_required,
+        )?;
+    }
+
+    Ok((total_wrapping_required, total_ribbon_required))
+}
+
+/// Same as `solve`, but over a stream of orders, stopping at the first error
+pub fn solve_stream<I: IntoIterator<Item = io::Result<(Present, NonZeroUsize)>>>(
+    orders: I,
+) -> io::Result<(Area, Length)> {
+    let mut total_wrapping_required = Area::default();
+    let mut total_ribbon_required = Length::default();
+    for order in orders {
+        let (present, quantity) = order?;
+
+        (total_wrapping_required, total_ribbon_required) = add_order(
+            &present,
+            quantity,
+            &Rules2015,
+            total_wrapping_required,
+            total_ribbon_required,
+        )?;
+    }
+
+    Ok((total_wrapping_required, total_ribbon_required))
+}
+
+/// Adds what `quantity` identical presents need to the totals
+fn add_order(
+    present: &Present,
+    quantity: NonZeroUsize,
+    rules: &dyn PackagingRules,
+    wrapping: Area,
+    ribbon: Length,
+) -> Result<(Area, Length), ArithmeticOverflow> {
+    let quantity = quantity.get() as u128;
+    let wrapping = rules
+        .wrapping(present)?
+        .checked_mul(quantity)
+        .and_then(|order| wrapping.checked_add(order))
+        .ok_or(ArithmeticOverflow {
+            quantity: "total wrapping paper",
+        })?;
+    let ribbon = rules
+        .ribbon(present)?
+        .checked_mul(quantity)
+        .and_then(|order| ribbon.checked_add(order))
+        .ok_or(ArithmeticOverflow {
+            quantity: "total ribbon",
+        })?;
+
+    Ok((wrapping, ribbon))
+}
+
+/// Answer labels for the totals in the given unit
+fn labels(unit: Unit) -> (&'static str, &'static str) {
+    match unit {
+        Unit::Millimetre => (
+            "Total required amount of wrapping paper (square millimetres)",
+            "Total required amount of ribbon (millimetres)",
+        ),
+        Unit::Centimetre => (
+            "Total required amount of wrapping paper (square centimetres)",
+            "Total required amount of ribbon (centimetres)",
+        ),
+        Unit::Metre => (
+            "Total required amount of wrapping paper (square metres)",
+            "Total required amount of ribbon (metres)",
+        ),
+        Unit::Inch => (
+            "Total required amount of wrapping paper (square inches)",
+            "Total required amount of ribbon (inches)",
+        ),
+        Unit::Foot => (
+            "Total required amount of wrapping paper (square feet)",
+            "Total required amount of ribbon (feet)",
+        ),
+        Unit::Yard => (
+            "Total required amount of wrapping paper (square yards)",
+            "Total required amount of ribbon (yards)",
+        ),
+    }
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    run_with_options(file_path, ParseOptions::default(), Unit::Foot, &Rules2015)
+}
+
+/// Same as `run`, following other packaging rules and reporting the totals in `unit`
+pub fn run_with_options<T: AsRef<Path>>(
+    file_path: T,
+    options: ParseOptions,
+    unit: Unit,
+    rules: &dyn PackagingRules,
+) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let orders = parse_input_with_options(file_path, options)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (total_wrapping_required, total_ribbon_required) = solve_with_rules(&orders, rules)?;
+    let solution_time = t1.elapsed();
+
+    let (wrapping_label, ribbon_label) = labels(unit);
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new(wrapping_label, total_wrapping_required.display_in(unit)),
+            Answer::new(ribbon_label, total_ribbon_required.display_in(unit)),
+        ],
+    })
+}
@@ -0,0 +1,197 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::{io, process};
+
+use aoc2015_day02::{
+    BreakdownReport, ConfiguredRules, InputFormat, Length, ParseError, ParseOptions, PriceTable,
+    Unit,
+};
+use clap::{Parser, ValueEnum};
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    Text,
+    /// Only print the report as CSV tables
+    Csv,
+    /// Only print the report as JSON
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PackFormat {
+    Text,
+    /// Only print the placements as JSON, for 3D viewers
+    Json,
+}
+
+/// Parses container dimensions like "40x8x8" or "12mx2400mmx8ft"
+fn parse_container(dimensions: &str) -> Result<[Length; 3], String> {
+    let lengths = dimensions
+        .split('x')
+        .map(|length| length.trim().parse().map_err(|e| format!("{}", e)))
+        .collect::<Result<Vec<Length>, _>>()?;
+
+    lengths
+        .try_into()
+        .map_err(|lengths: Vec<_>| format!("Expected 3 dimensions, found {}", lengths.len()))
+}
+
+#[derive(Parser)]
+struct Cli {
+    /// Input file
+    #[arg(long, default_value = "years/2015/day02/input")]
+    input: PathBuf,
+
+    /// Format of the input: auto, dimensions, csv, tsv or json
+    #[arg(long, default_value = "auto")]
+    format: InputFormat,
+
+    /// Skip blank lines instead of failing on them
+    #[arg(long)]
+    skip_blank: bool,
+
+    /// Skip lines starting with '#'
+    #[arg(long)]
+    skip_comments: bool,
+
+    /// Unit to report the totals in: mm, cm, m, in, ft or yd
+    #[arg(long, default_value = "ft")]
+    unit: Unit,
+
+    /// JSON file choosing other packaging rules than the 2015 ones
+    #[arg(long)]
+    rules: Option<PathBuf>,
+
+    /// JSON price table of paper rolls and ribbon spools, to also estimate what they cost
+    #[arg(long)]
+    prices: Option<PathBuf>,
+
+    /// Also report what every present needs, with aggregate statistics
+    #[arg(long, value_enum)]
+    report: Option<ReportFormat>,
+
+    /// Number of most expensive presents in the report
+    #[arg(long, default_value_t = 5)]
+    top: usize,
+
+    /// Also pack the presents into as few containers as a heuristic finds
+    #[arg(long, value_enum, requires = "container", conflicts_with = "report")]
+    pack: Option<PackFormat>,
+
+    /// Size of the containers to pack, like 40x8x8
+    #[arg(long, value_parser = parse_container)]
+    container: Option<[Length; 3]>,
+}
+
+/// Prints a line that isn't a present with its line and column, like `aoc check` does, and exits
+fn exit_on_parse_error(error: &(dyn Error + 'static), input: &Path) {
+    let Some(parse_error) = error
+        .downcast_ref::<io::Error>()
+        .and_then(io::Error::get_ref)
+        .and_then(|inner| inner.downcast_ref::<ParseError>())
+    else {
+        return;
+    };
+
+    eprintln!("{}: {}", input.display(), parse_error);
+    process::exit(1);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let options = ParseOptions {
+        skip_blank_lines: cli.skip_blank,
+        skip_comments: cli.skip_comments,
+        format: cli.format,
+    };
+
+    let parse_orders = || {
+        aoc2015_day02::parse_input_with_options(&cli.input, options)
+            .inspect_err(|e| exit_on_parse_error(e, &cli.input))
+    };
+
+    let rules = match &cli.rules {
+        Some(path) => ConfiguredRules::load(path)?,
+        None => ConfiguredRules::default(),
+    };
+
+    if let (Some(PackFormat::Json), Some(container)) = (cli.pack, cli.container) {
+        let orders = parse_orders()?;
+        let packing = aoc2015_day02::pack(&orders, container, cli.unit)?;
+        println!("{}", serde_json::to_string(&packing)?);
+
+        return Ok(());
+    }
+
+    if matches!(cli.report, Some(ReportFormat::Csv | ReportFormat::Json)) {
+        let orders = parse_orders()?;
+        let report = BreakdownReport::new(&orders, cli.unit, cli.top, &rules)?;
+        if cli.report == Some(ReportFormat::Csv) {
+            report.write_csv(io::stdout().lock())?;
+        } else {
+            println!("{}", serde_json::to_string(&report)?);
+        }
+
+        return Ok(());
+    }
+
+    let report = aoc2015_day02::run_with_options(&cli.input, options, cli.unit, &rules)
+        .inspect_err(|e| exit_on_parse_error(e.as_ref(), &cli.input))?;
+    report.print();
+
+    if cli.report.is_some() {
+        let orders = parse_orders()?;
+        let report = BreakdownReport::new(&orders, cli.unit, cli.top, &rules)?;
+        println!("Breakdown:\n{}", report);
+    }
+
+    if let Some(path) = &cli.prices {
+        let prices = PriceTable::load(path)?;
+        let orders = parse_orders()?;
+        let estimate = aoc2015_day02::estimate_cost(&orders, &prices, &rules)?;
+        println!(
+            "{}Cost:\nRolls of paper: {}\nSpools of ribbon: {}\nTotal cost: {:.2}\nPaper waste: {:.1}%\nRibbon waste: {:.1}%",
+            if cli.report.is_some() { "\n" } else { "" },
+            estimate.paper_rolls,
+            estimate.ribbon_spools,
+            estimate.total_cost,
+            estimate.paper_waste,
+            estimate.ribbon_waste
+        );
+    }
+
+    if let (Some(PackFormat::Text), Some(container)) = (cli.pack, cli.container) {
+        let orders = parse_orders()?;
+        let packing = aoc2015_day02::pack(&orders, container, cli.unit)?;
+
+        let fill_ratios: Vec<_> = packing
+            .containers
+            .iter()
+            .map(|container| format!("{:.1}%", container.fill_ratio * 100.0))
+            .collect();
+        println!(
+            "{}Packing:\nContainers used: {}\nFill ratios: {}",
+            if cli.prices.is_some() { "\n" } else { "" },
+            packing.containers.len(),
+            fill_ratios.join(", ")
+        );
+        for (i, container) in packing.containers.iter().enumerate() {
+            let placements: Vec<_> = container
+                .placements
+                .iter()
+                .map(|placement| {
+                    let [x, y, z] = placement.position;
+                    let [length, width, height] = placement.size;
+                    format!(
+                        "{} at ({}, {}, {}) as {}x{}x{}",
+                        placement.present, x, y, z, length, width, height
+                    )
+                })
+                .collect();
+            println!("Container {}: {}", i + 1, placements.join(", "));
+        }
+    }
+
+    Ok(())
+}
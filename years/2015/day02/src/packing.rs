@@ -0,0 +1,225 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+use serde::Serialize;
+
+use crate::{ArithmeticOverflow, Length, Present, Unit, Volume};
+
+#[derive(Debug)]
+pub enum PackingError {
+    /// More presents are ordered than `MAX_PRESENTS`, which are placed one at a time
+    TooMany,
+    /// The presents of the 1-based order don't fit in an empty container, however it's turned
+    TooLarge(usize),
+    Overflow(ArithmeticOverflow),
+}
+
+impl fmt::Display for PackingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PackingError::TooMany => {
+                write!(f, "Can't pack more than {} presents", MAX_PRESENTS)
+            }
+            PackingError::TooLarge(present) => {
+                write!(f, "Present {} doesn't fit in a container", present)
+            }
+            PackingError::Overflow(overflow) => write!(f, "{}", overflow),
+        }
+    }
+}
+
+impl Error for PackingError {}
+
+impl From<ArithmeticOverflow> for PackingError {
+    fn from(overflow: ArithmeticOverflow) -> Self {
+        PackingError::Overflow(overflow)
+    }
+}
+
+/// A present's box, turned to the given size, with its lowest corner at `position`
+#[derive(Serialize, Debug)]
+pub struct Placement {
+    /// 1-based position of the present's order in the input
+    pub present: usize,
+    /// Which of the ordered presents this is, from 1
+    pub copy: usize,
+    pub position: [f64; 3],
+    pub size: [f64; 3],
+}
+
+#[derive(Serialize, Debug)]
+pub struct PackedContainer {
+    /// Fraction of the container's volume filled by presents
+    pub fill_ratio: f64,
+    pub placements: Vec<Placement>,
+}
+
+/// Presents packed into containers, in the unit the packing was made for
+#[derive(Serialize, Debug)]
+pub struct Packing {
+    pub unit: String,
+    pub container: [f64; 3],
+    pub containers: Vec<PackedContainer>,
+}
+
+/// An exact box in micrometres, from `min` to `max` on every axis
+#[derive(Clone, Copy)]
+struct Cuboid {
+    min: [u128; 3],
+    max: [u128; 3],
+}
+
+impl Cuboid {
+    fn overlaps(&self, other: &Cuboid) -> bool {
+        (0..3).all(|axis| self.min[axis] < other.max[axis] && other.min[axis] < self.max[axis])
+    }
+}
+
+#[derive(Default)]
+struct Bin {
+    /// The order and copy of every present placed, with its box
+    boxes: Vec<((usize, usize), Cuboid)>,
+    /// Corners new boxes can be placed at, ordered bottom first, then back, then left
+    extreme_points: BTreeSet<[u128; 3]>,
+    free_volume: u128,
+}
+
+/// Most presents that can be packed, as every one is placed on its own
+pub const MAX_PRESENTS: usize = 100_000;
+
+/// Packs every ordered present into as few containers of the given size as a heuristic finds,
+/// turning them freely
+///
+/// Presents are placed largest first, each into the first container with room for it, at the
+/// first of the container's extreme points, the corners of the boxes already placed, where
+/// one of its rotations fits without overlapping anything.
+pub fn pack(
+    orders: &[(Present, NonZeroUsize)],
+    container: [Length; 3],
+    unit: Unit,
+) -> Result<Packing, PackingError> {
+    let overflow = ArithmeticOverflow {
+        quantity: "container volume",
+    };
+    let size = container.map(Length::micrometres);
+    let container_volume = Volume::from_sides(container[0], container[1], container[2])
+        .ok_or(overflow)?
+        .cubic_micrometres();
+
+    orders
+        .iter()
+        .try_fold(0usize, |total, (_, quantity)| {
+            total.checked_add(quantity.get())
+        })
+        .filter(|&total| total <= MAX_PRESENTS)
+        .ok_or(PackingError::TooMany)?;
+
+    let volumes = orders
+        .iter()
+        .map(|(present, _)| present.volume().map(Volume::cubic_micrometres))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut presents: Vec<_> = orders
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (_, quantity))| (0..quantity.get()).map(move |copy| (i, copy)))
+        .collect();
+    presents.sort_by_key(|&(i, copy)| (std::cmp::Reverse(volumes[i]), i, copy));
+
+    let mut bins: Vec<Bin> = Vec::new();
+    for (i, copy) in presents {
+        let sides = orders[i].0.dimensions().map(Length::micrometres);
+        if volumes[i] > container_volume {
+            return Err(PackingError::TooLarge(i + 1));
+        }
+
+        let placed = bins
+            .iter_mut()
+            .filter(|bin| bin.free_volume >= volumes[i])
+            .any(|bin| place(bin, (i, copy), sides, volumes[i], size));
+        if !placed {
+            let mut bin = Bin {
+                extreme_points: BTreeSet::from([[0; 3]]),
+                free_volume: container_volume,
+                ..Default::default()
+            };
+            if !place(&mut bin, (i, copy), sides, volumes[i], size) {
+                return Err(PackingError::TooLarge(i + 1));
+            }
+            bins.push(bin);
+        }
+    }
+
+    let convert = |value: u128| value as f64 / unit.micrometres() as f64;
+    Ok(Packing {
+        unit: unit.to_string(),
+        container: size.map(convert),
+        containers: bins
+            .into_iter()
+            .map(|bin| PackedContainer {
+                fill_ratio: 1.0 - bin.free_volume as f64 / container_volume as f64,
+                placements: bin
+                    .boxes
+                    .into_iter()
+                    .map(|((present, copy), cuboid)| Placement {
+                        present: present + 1,
+                        copy: copy + 1,
+                        position: cuboid.min.map(convert),
+                        size: [0, 1, 2].map(|axis| convert(cuboid.max[axis] - cuboid.min[axis])),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Places the present at the first extreme point where it fits, if any
+fn place(
+    bin: &mut Bin,
+    present: (usize, usize),
+    [a, b, c]: [u128; 3],
+    volume: u128,
+    size: [u128; 3],
+) -> bool {
+    const ROTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [1, 0, 2],
+        [0, 2, 1],
+        [2, 0, 1],
+        [1, 2, 0],
+        [2, 1, 0],
+    ];
+    let sides = [a, b, c];
+
+    // The extreme points are stored as [z, y, x] so they're ordered bottom first
+    for &[z, y, x] in &bin.extreme_points {
+        let min = [x, y, z];
+        for rotation in ROTATIONS {
+            let extent = rotation.map(|side| sides[side]);
+            let cuboid = Cuboid {
+                min,
+                max: [0, 1, 2].map(|axis| min[axis] + extent[axis]),
+            };
+            if (0..3).any(|axis| cuboid.max[axis] > size[axis])
+                || bin.boxes.iter().any(|(_, other)| cuboid.overlaps(other))
+            {
+                continue;
+            }
+
+            bin.extreme_points.remove(&[z, y, x]);
+            let [max_x, max_y, max_z] = cuboid.max;
+            for [x, y, z] in [[max_x, y, z], [x, max_y, z], [x, y, max_z]] {
+                if x < size[0] && y < size[1] && z < size[2] {
+                    bin.extreme_points.insert([z, y, x]);
+                }
+            }
+            bin.boxes.push((present, cuboid));
+            bin.free_volume -= volume;
+
+            return true;
+        }
+    }
+
+    false
+}
@@ -0,0 +1,584 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::iter;
+use std::num::{IntErrorKind, NonZeroUsize};
+use std::path::Path;
+use std::str::FromStr;
+
+use common::check;
+use serde::Deserialize;
+
+use crate::{Length, Present, Unit, UnknownUnit};
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum InputFormat {
+    /// Guess the format from the start of the input
+    #[default]
+    Auto,
+    /// `LxWxH` lines, optionally followed by `*quantity`
+    Dimensions,
+    /// `l,w,h` lines, optionally followed by `,quantity`, with an optional header line
+    Csv,
+    /// Same as `Csv`, separated by tabs
+    Tsv,
+    /// An array of objects with `length`, `width`, `height` and an optional `quantity`
+    Json,
+}
+
+impl InputFormat {
+    /// JSON if the input starts with '[', else CSV or TSV if its first line splits into 3 or 4
+    /// fields on a comma or a tab, else dimensions
+    pub fn detect(start: &[u8]) -> Self {
+        let start = String::from_utf8_lossy(start);
+        if start.trim_start().starts_with('[') {
+            return InputFormat::Json;
+        }
+
+        let first_line = start
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .unwrap_or_default();
+        // Trailing whitespace around dimensions isn't a separator
+        let splits_on = |separator| {
+            let fields: Vec<_> = first_line.split(separator).map(str::trim).collect();
+            (3..=4).contains(&fields.len()) && fields.iter().all(|field| !field.is_empty())
+        };
+        if splits_on('\t') {
+            InputFormat::Tsv
+        } else if splits_on(',') {
+            InputFormat::Csv
+        } else {
+            InputFormat::Dimensions
+        }
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(InputFormat::Auto),
+            "dimensions" => Ok(InputFormat::Dimensions),
+            "csv" => Ok(InputFormat::Csv),
+            "tsv" => Ok(InputFormat::Tsv),
+            "json" => Ok(InputFormat::Json),
+            _ => Err(format!(
+                "Unknown format {:?}, expected one of auto, dimensions, csv, tsv or json",
+                s
+            )),
+        }
+    }
+}
+
+/// How to read the input, and the lines the parser skips instead of rejecting
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ParseOptions {
+    pub skip_blank_lines: bool,
+    /// Skip lines starting with '#'
+    pub skip_comments: bool,
+    pub format: InputFormat,
+}
+
+#[derive(Debug)]
+pub enum ParseErrorKind {
+    BlankLine,
+    WrongDimensionCount(usize),
+    MissingDimension,
+    InvalidDimension(String),
+    ZeroDimension,
+    NegativeDimension,
+    DimensionOverflow,
+    UnknownUnit(String),
+    WrongFieldCount(usize),
+    InvalidQuantity(String),
+    Json(String),
+}
+
+/// A line that isn't a present, with the 1-based line and column of the offending field
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::BlankLine => write!(f, "Blank line"),
+            ParseErrorKind::WrongDimensionCount(count) => {
+                write!(f, "Expected 3 dimensions, found {}", count)
+            }
+            ParseErrorKind::MissingDimension => write!(f, "Missing dimension"),
+            ParseErrorKind::InvalidDimension(field) => write!(f, "Invalid dimension {:?}", field),
+            ParseErrorKind::ZeroDimension => write!(f, "Dimension is zero"),
+            ParseErrorKind::NegativeDimension => write!(f, "Dimension is negative"),
+            ParseErrorKind::DimensionOverflow => write!(f, "Dimension is too large"),
+            ParseErrorKind::UnknownUnit(unit) => write!(f, "{}", UnknownUnit(unit.clone())),
+            ParseErrorKind::WrongFieldCount(count) => write!(
+                f,
+                "Expected 3 dimensions and an optional quantity, found {} fields",
+                count
+            ),
+            ParseErrorKind::InvalidQuantity(quantity) => {
+                write!(
+                    f,
+                    "Invalid quantity {:?}, expected a positive number",
+                    quantity
+                )
+            }
+            ParseErrorKind::Json(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Error for ParseErrorKind {}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(error: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<Vec<(Present, NonZeroUsize)>> {
+    parse_input_with_options(file_path, ParseOptions::default())
+}
+
+pub fn parse_input_with_options<T: AsRef<Path>>(
+    file_path: T,
+    options: ParseOptions,
+) -> io::Result<Vec<(Present, NonZeroUsize)>> {
+    // Open input file
+    let input = File::open(file_path)?;
+
+    parse_reader_with_options(BufReader::new(input), options)
+}
+
+pub fn parse_reader<R: BufRead>(reader: R) -> io::Result<Vec<(Present, NonZeroUsize)>> {
+    parse_reader_with_options(reader, ParseOptions::default())
+}
+
+pub fn parse_reader_with_options<R: BufRead>(
+    reader: R,
+    options: ParseOptions,
+) -> io::Result<Vec<(Present, NonZeroUsize)>> {
+    parse_stream_with_options(reader, options).collect()
+}
+
+/// Lazily parses the presents one line at a time, with how many of each are ordered, so inputs of
+/// any size take constant memory
+///
+/// JSON inputs are the exception, and are read whole.
+pub fn parse_stream<'a, R: BufRead + 'a>(
+    reader: R,
+) -> impl Iterator<Item = io::Result<(Present, NonZeroUsize)>> + 'a {
+    parse_stream_with_options(reader, ParseOptions::default())
+}
+
+pub fn parse_stream_with_options<'a, R: BufRead + 'a>(
+    mut reader: R,
+    mut options: ParseOptions,
+) -> impl Iterator<Item = io::Result<(Present, NonZeroUsize)>> + 'a {
+    if options.format == InputFormat::Auto {
+        options.format = match reader.fill_buf() {
+            Ok(start) => InputFormat::detect(start),
+            Err(e) => return Box::new(iter::once(Err(e))) as Box<dyn Iterator<Item = _> + 'a>,
+        };
+    }
+
+    if options.format == InputFormat::Json {
+        let mut input = String::new();
+        match reader.read_to_string(&mut input) {
+            Ok(_) => match parse_json(&input) {
+                Ok(orders) => Box::new(orders.into_iter().map(Ok)),
+                Err(e) => Box::new(iter::once(Err(e.into()))),
+            },
+            Err(e) => Box::new(iter::once(Err(e))),
+        }
+    } else {
+        Box::new(reader.lines().enumerate().filter_map(move |(i, line)| {
+            match line {
+                Ok(line) => parse_present(&line, i + 1, options)
+                    .map_err(io::Error::from)
+                    .transpose(),
+                Err(e) => Some(Err(e)),
+            }
+        }))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonPresent {
+    #[serde(alias = "l")]
+    length: Length,
+    #[serde(alias = "w")]
+    width: Length,
+    #[serde(alias = "h")]
+    height: Length,
+    #[serde(default = "one", alias = "qty")]
+    quantity: NonZeroUsize,
+}
+
+fn one() -> NonZeroUsize {
+    NonZeroUsize::MIN
+}
+
+/// Parses a JSON array of presents, with their quantities
+pub fn parse_json(input: &str) -> Result<Vec<(Present, NonZeroUsize)>, ParseError> {
+    let presents: Vec<JsonPresent> = serde_json::from_str(input).map_err(|e| {
+        // Keep the position out of the message, as it's already in the error
+        let message = e.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message);
+
+        ParseError {
+            line: e.line(),
+            column: e.column(),
+            kind: ParseErrorKind::Json(message.to_string()),
+        }
+    })?;
+
+    Ok(presents
+        .into_iter()
+        .map(|present| {
+            (
+                Present::with_lengths(present.length, present.width, present.height),
+                present.quantity,
+            )
+        })
+        .collect())
+}
+
+/// Parses a line like "2x3x4", "2ftx3inx4cm*10" or, in CSV, "2,3,4,10", allowing whitespace
+/// around the fields, and returns the present with its quantity
+///
+/// Dimensions without a unit are in feet, and lines without a quantity are a single present.
+/// Lines of JSON and undetected formats are read as dimensions.
+///
+/// Returns `None` for the lines the options say to skip, and for a CSV or TSV header.
+pub fn parse_present(
+    line: &str,
+    line_number: usize,
+    options: ParseOptions,
+) -> Result<Option<(Present, NonZeroUsize)>, ParseError> {
+    let error = |field: &str, kind| ParseError {
+        line: line_number,
+        column: check::column_of(line, field),
+        kind,
+    };
+
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        if options.skip_blank_lines {
+            return Ok(None);
+        }
+        return Err(error(line, ParseErrorKind::BlankLine));
+    }
+    if options.skip_comments && trimmed.starts_with('#') {
+        return Ok(None);
+    }
+
+    let (fields, quantity) = match options.format {
+        InputFormat::Csv | InputFormat::Tsv => {
+            let separator = if options.format == InputFormat::Csv {
+                ','
+            } else {
+                '\t'
+            };
+            // Spreadsheets may quote every field
+            let fields: Vec<_> = line
+                .split(separator)
+                .map(|field| field.trim().trim_matches('"'))
+                .collect();
+            if line_number == 1 && fields[0].starts_with(char::is_alphabetic) {
+                return Ok(None);
+            }
+
+            match fields[..] {
+                [length, width, height] => (vec![length, width, height], None),
+                [length, width, height, quantity] => (vec![length, width, height], Some(quantity)),
+                _ => return Err(error(line, ParseErrorKind::WrongFieldCount(fields.len()))),
+            }
+        }
+        _ => {
+            let (dimensions, quantity) = match line.split_once('*') {
+                Some((dimensions, quantity)) => (dimensions, Some(quantity)),
+                None => (line, None),
+            };
+
+            let fields: Vec<_> = dimensions.split('x').collect();
+            if fields.len() != 3 {
+                return Err(error(
+                    line,
+                    ParseErrorKind::WrongDimensionCount(fields.len()),
+                ));
+            }
+            (fields, quantity)
+        }
+    };
+
+    let parse_dimension = |field: &str| {
+        let dimension = field.trim();
+        if dimension.is_empty() {
+            return Err(error(field, ParseErrorKind::MissingDimension));
+        }
+
+        dimension.parse().map_err(|kind| error(dimension, kind))
+    };
+    let present = Present::with_lengths(
+        parse_dimension(fields[0])?,
+        parse_dimension(fields[1])?,
+        parse_dimension(fields[2])?,
+    );
+
+    let quantity = match quantity {
+        Some(field) => {
+            let quantity = field.trim();
+            quantity.parse().map_err(|_| {
+                error(
+                    quantity,
+                    ParseErrorKind::InvalidQuantity(quantity.to_string()),
+                )
+            })?
+        }
+        None => NonZeroUsize::MIN,
+    };
+
+    Ok(Some((present, quantity)))
+}
+
+impl FromStr for Length {
+    type Err = ParseErrorKind;
+
+    /// Parses a positive whole number of some unit, like "3in", defaulting to feet
+    fn from_str(dimension: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseErrorKind::InvalidDimension(dimension.to_string());
+
+        // The unit, if any, starts at the first letter
+        let (number, unit) = dimension.split_at(
+            dimension
+                .find(char::is_alphabetic)
+                .unwrap_or(dimension.len()),
+        );
+        let number = number.trim_end();
+        let unit = if unit.is_empty() {
+            Unit::Foot
+        } else if number.is_empty() {
+            return Err(invalid());
+        } else {
+            unit.parse()
+                .map_err(|UnknownUnit(unit)| ParseErrorKind::UnknownUnit(unit))?
+        };
+
+        match number.parse::<u128>() {
+            Ok(0) => Err(ParseErrorKind::ZeroDimension),
+            Ok(value) => Length::new(value, unit).ok_or(ParseErrorKind::DimensionOverflow),
+            Err(_) if number.starts_with('-') && number[1..].parse::<u128>().is_ok() => {
+                Err(ParseErrorKind::NegativeDimension)
+            }
+            Err(e) if *e.kind() == IntErrorKind::PosOverflow => {
+                Err(ParseErrorKind::DimensionOverflow)
+            }
+            Err(_) => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str, options: ParseOptions) -> ParseError {
+        let error = parse_reader_with_options(input.as_bytes(), options).unwrap_err();
+        *error.into_inner().unwrap().downcast().unwrap()
+    }
+
+    /// The 1-based line and column of the first error in the input, read as dimensions
+    fn error_at(input: &str) -> (usize, usize, ParseErrorKind) {
+        let options = ParseOptions {
+            format: InputFormat::Dimensions,
+            ..Default::default()
+        };
+        let error = parse_error(input, options);
+        (error.line, error.column, error.kind)
+    }
+
+    #[test]
+    fn reports_blank_lines() {
+        assert!(matches!(
+            error_at("2x3x4\n\n1x1x1"),
+            (2, 1, ParseErrorKind::BlankLine)
+        ));
+    }
+
+    #[test]
+    fn reports_missing_or_extra_dimensions() {
+        assert!(matches!(
+            error_at("2x3x4\n2x3"),
+            (2, 1, ParseErrorKind::WrongDimensionCount(2))
+        ));
+        assert!(matches!(
+            error_at("2x3x4x5"),
+            (1, 1, ParseErrorKind::WrongDimensionCount(4))
+        ));
+        assert!(matches!(
+            error_at("2x x4"),
+            (1, 3, ParseErrorKind::MissingDimension)
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_dimensions() {
+        assert!(matches!(
+            error_at("2 x a x 4"),
+            (1, 5, ParseErrorKind::InvalidDimension(field)) if field == "a"
+        ));
+        assert!(matches!(
+            error_at(" 2 x 0 x 4"),
+            (1, 6, ParseErrorKind::ZeroDimension)
+        ));
+        assert!(matches!(
+            error_at("2x-3x4"),
+            (1, 3, ParseErrorKind::NegativeDimension)
+        ));
+        assert!(matches!(
+            error_at("2x3x99999999999999999999999999999999999999999"),
+            (1, 5, ParseErrorKind::DimensionOverflow)
+        ));
+        // Fits in 128 bits, but not once in micrometres
+        assert!(matches!(
+            error_at("2x3x340282366920938463463374607431768211455"),
+            (1, 5, ParseErrorKind::DimensionOverflow)
+        ));
+        assert!(matches!(
+            error_at("2x3furlongx4"),
+            (1, 3, ParseErrorKind::UnknownUnit(unit)) if unit == "furlong"
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_quantities() {
+        assert!(matches!(
+            error_at("2x3x4 * 0"),
+            (1, 9, ParseErrorKind::InvalidQuantity(quantity)) if quantity == "0"
+        ));
+        assert!(matches!(
+            error_at("2x3x4*-1"),
+            (1, 7, ParseErrorKind::InvalidQuantity(_))
+        ));
+    }
+
+    #[test]
+    fn reports_missing_or_extra_fields() {
+        for (input, format) in [("2,3\n", InputFormat::Csv), ("2\t3\n", InputFormat::Tsv)] {
+            let options = ParseOptions {
+                format,
+                ..Default::default()
+            };
+            let error = parse_error(input, options);
+            assert!(matches!(
+                (error.line, error.column, error.kind),
+                (1, 1, ParseErrorKind::WrongFieldCount(2))
+            ));
+        }
+
+        let options = ParseOptions {
+            format: InputFormat::Csv,
+            ..Default::default()
+        };
+        let error = parse_error("l,w,h\n2,3,4\n2, 3, 4, 5, 6", options);
+        assert!(matches!(
+            (error.line, error.column, error.kind),
+            (3, 1, ParseErrorKind::WrongFieldCount(5))
+        ));
+        let error = parse_error("2,3,4\n2,0,4", options);
+        assert!(matches!(
+            (error.line, error.column, error.kind),
+            (2, 3, ParseErrorKind::ZeroDimension)
+        ));
+    }
+
+    #[test]
+    fn reports_json_errors_at_their_position() {
+        let error = parse_json("[\n  {\"l\": 2, \"w\": 3}\n]").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(matches!(error.kind, ParseErrorKind::Json(_)));
+    }
+
+    #[test]
+    fn skips_blank_lines_and_comments_keeping_line_numbers() {
+        let input = "# presents\n\n2x3x4\n   \n# more\n1x1x10*2\n";
+        let options = ParseOptions {
+            skip_blank_lines: true,
+            skip_comments: true,
+            format: InputFormat::Dimensions,
+        };
+        let orders = parse_reader_with_options(input.as_bytes(), options).unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[1].1.get(), 2);
+
+        let error = parse_error(&format!("{}1x0x1\n", input), options);
+        assert!(matches!(
+            (error.line, error.column, error.kind),
+            (7, 3, ParseErrorKind::ZeroDimension)
+        ));
+
+        // Without the options, both are errors
+        assert!(matches!(
+            error_at("2x3x4\n# more"),
+            (2, 1, ParseErrorKind::WrongDimensionCount(1))
+        ));
+    }
+
+    #[test]
+    fn detects_every_format() {
+        for (start, format) in [
+            ("2x3x4\n1x1x10\n", InputFormat::Dimensions),
+            ("2ftx3inx4cm*10\n", InputFormat::Dimensions),
+            ("# presents\n\n2x3x4\n", InputFormat::Dimensions),
+            ("2,3,4\n", InputFormat::Csv),
+            ("length,width,height,quantity\n2,3,4,10\n", InputFormat::Csv),
+            ("\"2\", \"3\", \"4\"\n", InputFormat::Csv),
+            ("2\t3\t4\n", InputFormat::Tsv),
+            ("l\tw\th\tqty\n2\t3\t4\t1\n", InputFormat::Tsv),
+            ("2,5\t3\t4\n", InputFormat::Tsv),
+            ("  [{\"l\": 2, \"w\": 3, \"h\": 4}]", InputFormat::Json),
+            ("", InputFormat::Dimensions),
+        ] {
+            assert_eq!(InputFormat::detect(start.as_bytes()), format, "{:?}", start);
+        }
+    }
+
+    #[test]
+    fn detects_dimensions_with_stray_separators() {
+        for start in ["2x3x4\t\n", "2x3x4 ,\n", "\t2x3x4\n", "2x3x4,10\n"] {
+            assert_eq!(
+                InputFormat::detect(start.as_bytes()),
+                InputFormat::Dimensions,
+                "{:?}",
+                start
+            );
+        }
+
+        let orders = parse_reader("2x3x4\t\n1x1x10\n".as_bytes()).unwrap();
+        assert_eq!(orders.len(), 2);
+    }
+}
@@ -0,0 +1,347 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::io::{self, Write};
+use std::num::NonZeroUsize;
+
+use serde::Serialize;
+
+use crate::{Area, ArithmeticOverflow, Length, PackagingRules, Present, Unit, Volume};
+
+/// What a single present needs, in the unit of the report
+#[derive(Serialize, Debug)]
+pub struct PresentBreakdown {
+    /// 1-based position of the present's order in the input
+    pub present: usize,
+    /// How many of the present are ordered
+    pub quantity: usize,
+    pub length: f64,
+    pub width: f64,
+    pub height: f64,
+    pub surface_area: f64,
+    /// Paper on top of the surface area, `None` when the rules don't add it to the surface area
+    pub slack: Option<f64>,
+    pub wrapping: f64,
+    pub ribbon_perimeter: f64,
+    /// Ribbon on top of the perimeter, `None` when the rules don't add it to the perimeter
+    pub bow: Option<f64>,
+    pub ribbon: f64,
+    pub volume: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Statistics {
+    pub mean: f64,
+    pub median: f64,
+    pub max: f64,
+}
+
+/// Number of presents with a volume in `[min_volume, max_volume)`
+#[derive(Serialize, Debug)]
+pub struct VolumeBucket {
+    pub min_volume: f64,
+    pub max_volume: f64,
+    pub presents: usize,
+}
+
+/// Per-present breakdown of the wrapping paper and ribbon, with aggregate statistics
+#[derive(Serialize, Debug)]
+pub struct BreakdownReport {
+    pub unit: String,
+    pub presents: Vec<PresentBreakdown>,
+    /// `None` when there are no presents
+    pub wrapping: Option<Statistics>,
+    pub ribbon: Option<Statistics>,
+    /// Orders of the presents needing the most wrapping paper, then the most ribbon
+    pub most_expensive: Vec<usize>,
+    /// Power of two buckets of volume, from the smallest present to the largest
+    pub volume_histogram: Vec<VolumeBucket>,
+}
+
+impl BreakdownReport {
+    /// Computes everything exactly, only converting to `unit` at the end
+    ///
+    /// The wrapping paper, ribbon, slack and bow all follow `rules`. Every order is listed once,
+    /// while the statistics and histogram count each of its presents.
+    pub fn new(
+        orders: &[(Present, NonZeroUsize)],
+        unit: Unit,
+        top: usize,
+        rules: &dyn PackagingRules,
+    ) -> Result<Self, ArithmeticOverflow> {
+        let mut wrapping = Vec::with_capacity(orders.len());
+        let mut ribbon = Vec::with_capacity(orders.len());
+        let mut volumes = Vec::with_capacity(orders.len());
+        let mut breakdowns = Vec::with_capacity(orders.len());
+        for (i, (present, quantity)) in orders.iter().enumerate() {
+            let present_wrapping = rules.wrapping(present)?;
+            let present_ribbon = rules.ribbon(present)?;
+            let [length, width, height] = present.dimensions();
+            let breakdown = PresentBreakdown {
+                present: i + 1,
+                quantity: quantity.get(),
+                length: length.in_unit(unit),
+                width: width.in_unit(unit),
+                height: height.in_unit(unit),
+                surface_area: present.surface_area()?.in_unit(unit),
+                slack: rules.slack(present)?.map(|slack| slack.in_unit(unit)),
+                wrapping: present_wrapping.in_unit(unit),
+                ribbon_perimeter: present.ribbon_perimeter()?.in_unit(unit),
+                bow: rules.bow(present)?.map(|bow| bow.in_unit(unit)),
+                ribbon: present_ribbon.in_unit(unit),
+                volume: present.volume()?.in_unit(unit),
+            };
+
+            wrapping.push((present_wrapping, *quantity));
+            ribbon.push((present_ribbon, *quantity));
+            volumes.push((present.volume()?, *quantity));
+            breakdowns.push(breakdown);
+        }
+
+        let mut ranking: Vec<_> = (0..orders.len()).collect();
+        ranking.sort_by_key(|&i| (Reverse(wrapping[i].0), Reverse(ribbon[i].0), i));
+        let most_expensive = ranking.into_iter().take(top).map(|i| i + 1).collect();
+
+        Ok(BreakdownReport {
+            unit: unit.to_string(),
+            presents: breakdowns,
+            wrapping: area_statistics(&wrapping, unit)?,
+            ribbon: length_statistics(&ribbon, unit)?,
+            most_expensive,
+            volume_histogram: volume_histogram(&volumes, unit)?,
+        })
+    }
+
+    /// Writes the breakdown, statistics, ranking and histogram as CSV tables separated by blank lines
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let u = &self.unit;
+        writeln!(
+            writer,
+            "present,quantity,length_{u},width_{u},height_{u},surface_area_{u}2,slack_{u}2,wrapping_{u}2,ribbon_perimeter_{u},bow_{u},ribbon_{u},volume_{u}3"
+        )?;
+        for p in &self.presents {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                p.present,
+                p.quantity,
+                p.length,
+                p.width,
+                p.height,
+                p.surface_area,
+                p.slack.map(|slack| slack.to_string()).unwrap_or_default(),
+                p.wrapping,
+                p.ribbon_perimeter,
+                p.bow.map(|bow| bow.to_string()).unwrap_or_default(),
+                p.ribbon,
+                p.volume
+            )?;
+        }
+
+        writeln!(writer, "\nstatistic,wrapping_{u}2,ribbon_{u}")?;
+        if let (Some(wrapping), Some(ribbon)) = (&self.wrapping, &self.ribbon) {
+            writeln!(writer, "mean,{},{}", wrapping.mean, ribbon.mean)?;
+            writeln!(writer, "median,{},{}", wrapping.median, ribbon.median)?;
+            writeln!(writer, "max,{},{}", wrapping.max, ribbon.max)?;
+        }
+
+        writeln!(writer, "\nrank,present")?;
+        for (rank, present) in self.most_expensive.iter().enumerate() {
+            writeln!(writer, "{},{}", rank + 1, present)?;
+        }
+
+        writeln!(writer, "\nmin_volume_{u}3,max_volume_{u}3,presents")?;
+        for bucket in &self.volume_histogram {
+            writeln!(
+                writer,
+                "{},{},{}",
+                bucket.min_volume, bucket.max_volume, bucket.presents
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for BreakdownReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let u = &self.unit;
+        writeln!(
+            f,
+            "{:>8} {:>8} {:>20} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "Present",
+            "Quantity",
+            format!("Dimensions ({u})"),
+            format!("Area ({u}2)"),
+            format!("Slack ({u}2)"),
+            format!("Paper ({u}2)"),
+            format!("Around ({u})"),
+            format!("Bow ({u})"),
+            format!("Ribbon ({u})"),
+            format!("Volume ({u}3)")
+        )?;
+        for p in &self.presents {
+            writeln!(
+                f,
+                "{:>8} {:>8} {:>20} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                p.present,
+                p.quantity,
+                format!("{}x{}x{}", round(p.length), round(p.width), round(p.height)),
+                round(p.surface_area),
+                p.slack
+                    .map_or("-".to_string(), |slack| round(slack).to_string()),
+                round(p.wrapping),
+                round(p.ribbon_perimeter),
+                p.bow.map_or("-".to_string(), |bow| round(bow).to_string()),
+                round(p.ribbon),
+                round(p.volume)
+            )?;
+        }
+
+        if let (Some(wrapping), Some(ribbon)) = (&self.wrapping, &self.ribbon) {
+            writeln!(
+                f,
+                "\nWrapping paper ({u}2): mean {}, median {}, max {}",
+                round(wrapping.mean),
+                round(wrapping.median),
+                round(wrapping.max)
+            )?;
+            writeln!(
+                f,
+                "Ribbon ({u}): mean {}, median {}, max {}",
+                round(ribbon.mean),
+                round(ribbon.median),
+                round(ribbon.max)
+            )?;
+        }
+
+        let most_expensive: Vec<_> = self.most_expensive.iter().map(|p| p.to_string()).collect();
+        writeln!(f, "Most expensive presents: {}", most_expensive.join(", "))?;
+
+        write!(f, "Presents by volume ({u}3):")?;
+        for bucket in &self.volume_histogram {
+            write!(
+                f,
+                "\n{:>12} to {:<12} {}",
+                round(bucket.min_volume),
+                round(bucket.max_volume),
+                bucket.presents
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Rounds to 3 decimals for display, dropping trailing zeros
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+fn area_statistics(
+    areas: &[(Area, NonZeroUsize)],
+    unit: Unit,
+) -> Result<Option<Statistics>, ArithmeticOverflow> {
+    let values: Vec<_> = areas
+        .iter()
+        .map(|(area, quantity)| (area.square_micrometres(), quantity.get() as u128))
+        .collect();
+    let divisor = (unit.micrometres() * unit.micrometres()) as f64;
+
+    statistics(values, divisor, "total wrapping paper")
+}
+
+fn length_statistics(
+    lengths: &[(Length, NonZeroUsize)],
+    unit: Unit,
+) -> Result<Option<Statistics>, ArithmeticOverflow> {
+    let values: Vec<_> = lengths
+        .iter()
+        .map(|(length, quantity)| (length.micrometres(), quantity.get() as u128))
+        .collect();
+
+    statistics(values, unit.micrometres() as f64, "total ribbon")
+}
+
+/// Exact statistics over base unit values, each counted as many times as given, divided by
+/// `divisor` to convert them
+fn statistics(
+    mut values: Vec<(u128, u128)>,
+    divisor: f64,
+    quantity: &'static str,
+) -> Result<Option<Statistics>, ArithmeticOverflow> {
+    let Some(&(max, _)) = values.iter().max() else {
+        return Ok(None);
+    };
+    values.sort_unstable();
+
+    let (total, count) = values
+        .iter()
+        .try_fold((0u128, 0u128), |(total, count), &(value, times)| {
+            Some((
+                total.checked_add(value.checked_mul(times)?)?,
+                count.checked_add(times)?,
+            ))
+        })
+        .ok_or(ArithmeticOverflow { quantity })?;
+
+    // The median is the value covering the middle count
+    let mut seen = 0;
+    let median = values
+        .iter()
+        .find(|&&(_, times)| {
+            seen += times;
+            seen > count / 2
+        })
+        .map_or(max, |&(value, _)| value);
+
+    Ok(Some(Statistics {
+        mean: total as f64 / count as f64 / divisor,
+        median: median as f64 / divisor,
+        max: max as f64 / divisor,
+    }))
+}
+
+fn volume_histogram(
+    volumes: &[(Volume, NonZeroUsize)],
+    unit: Unit,
+) -> Result<Vec<VolumeBucket>, ArithmeticOverflow> {
+    // Bucket 0 holds everything under 1 cubic unit, bucket k holds [2^(k - 1), 2^k)
+    let bucket_of = |volume: &Volume| {
+        let volume = volume.in_unit(unit);
+        if volume < 1.0 {
+            0
+        } else {
+            volume.log2().floor() as i32 + 1
+        }
+    };
+    let (Some(first), Some(last)) = (
+        volumes.iter().map(|(volume, _)| bucket_of(volume)).min(),
+        volumes.iter().map(|(volume, _)| bucket_of(volume)).max(),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let mut counts = vec![0usize; (last - first + 1) as usize];
+    for (volume, quantity) in volumes {
+        let count = &mut counts[(bucket_of(volume) - first) as usize];
+        *count = count
+            .checked_add(quantity.get())
+            .ok_or(ArithmeticOverflow {
+                quantity: "number of presents",
+            })?;
+    }
+
+    Ok(counts
+        .into_iter()
+        .zip(first..)
+        .map(|(presents, bucket)| VolumeBucket {
+            min_volume: if bucket == 0 {
+                0.0
+            } else {
+                2f64.powi(bucket - 1)
+            },
+            max_volume: 2f64.powi(bucket),
+            presents,
+        })
+        .collect())
+}
@@ -0,0 +1,254 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+use serde::de::{self, Unexpected};
+use serde::{Deserialize, Deserializer};
+
+use crate::{Area, ArithmeticOverflow, Length, Present, RIBBON_OVERFLOW, WRAPPING_OVERFLOW};
+
+/// How much wrapping paper and ribbon a present needs
+pub trait PackagingRules {
+    fn wrapping(&self, present: &Present) -> Result<Area, ArithmeticOverflow>;
+
+    fn ribbon(&self, present: &Present) -> Result<Length, ArithmeticOverflow>;
+
+    /// The paper on top of the surface area, if `wrapping` is the surface area plus some
+    fn slack(&self, present: &Present) -> Result<Option<Area>, ArithmeticOverflow>;
+
+    /// The ribbon on top of the smallest perimeter, if `ribbon` is the perimeter plus a bow
+    fn bow(&self, present: &Present) -> Result<Option<Length>, ArithmeticOverflow>;
+
+    /// The pieces the wrapping is cut as, by their two sides, adding up to `wrapping`
+    fn paper_pieces(&self, present: &Present) -> Result<Vec<(Length, Length)>, ArithmeticOverflow>;
+
+    /// The pieces the ribbon is cut as, adding up to `ribbon`
+    fn ribbon_pieces(&self, present: &Present) -> Result<Vec<Length>, ArithmeticOverflow>;
+}
+
+/// The elves' rules from the 2015 puzzle, the default
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Rules2015;
+
+impl PackagingRules for Rules2015 {
+    fn wrapping(&self, present: &Present) -> Result<Area, ArithmeticOverflow> {
+        present.calculate_wrapping_amount()
+    }
+
+    fn ribbon(&self, present: &Present) -> Result<Length, ArithmeticOverflow> {
+        present.calculate_ribbon_amount()
+    }
+
+    fn slack(&self, present: &Present) -> Result<Option<Area>, ArithmeticOverflow> {
+        present.slack().map(Some)
+    }
+
+    fn bow(&self, present: &Present) -> Result<Option<Length>, ArithmeticOverflow> {
+        present.bow().map(Some)
+    }
+
+    fn paper_pieces(&self, present: &Present) -> Result<Vec<(Length, Length)>, ArithmeticOverflow> {
+        Ok(present.rectangles().to_vec())
+    }
+
+    fn ribbon_pieces(&self, present: &Present) -> Result<Vec<Length>, ArithmeticOverflow> {
+        Ok(vec![present.ribbon_perimeter()?, present.bow()?])
+    }
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WrappingRule {
+    /// Surface area plus the area of the smallest side
+    #[default]
+    SmallestSide,
+    /// Surface area plus a strip `margin` wide along the smallest perimeter, where the paper
+    /// overlaps
+    Overlap { margin: Length },
+    /// The material of the smallest open-topped bag the present fits in, with the bag sizes given
+    /// as width, depth and height. Presents too large for every bag are wrapped following the
+    /// 2015 rules.
+    GiftBag { sizes: Vec<[Length; 3]> },
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RibbonRule {
+    /// Smallest perimeter plus a bow of as many feet as cubic feet of volume
+    #[default]
+    VolumeBow,
+    /// Smallest perimeter plus a bow `factor` times the longest side
+    LongestSideBow {
+        #[serde(deserialize_with = "bow_factor")]
+        factor: f64,
+    },
+    None,
+}
+
+/// Rejects bow factors that are negative or not finite, which would make no sense as a length
+fn bow_factor<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let factor = f64::deserialize(deserializer)?;
+    if factor >= 0.0 && factor.is_finite() {
+        Ok(factor)
+    } else {
+        Err(de::Error::invalid_value(
+            Unexpected::Float(factor),
+            &"a finite factor of 0 or more",
+        ))
+    }
+}
+
+/// Rules chosen from the built-in alternatives, e.g. loaded from a JSON file such as
+/// `{"wrapping": {"overlap": {"margin": "2in"}}, "ribbon": {"longest_side_bow": {"factor": 1.5}}}`
+///
+/// Missing rules default to the 2015 ones.
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfiguredRules {
+    pub wrapping: WrappingRule,
+    pub ribbon: RibbonRule,
+}
+
+impl ConfiguredRules {
+    pub fn load<T: AsRef<Path>>(file_path: T) -> io::Result<Self> {
+        let config = BufReader::new(File::open(file_path)?);
+
+        Ok(serde_json::from_reader(config)?)
+    }
+}
+
+impl PackagingRules for ConfiguredRules {
+    fn wrapping(&self, present: &Present) -> Result<Area, ArithmeticOverflow> {
+        match &self.wrapping {
+            WrappingRule::SmallestSide => present.calculate_wrapping_amount(),
+            WrappingRule::Overlap { margin } => present
+                .surface_area()?
+                .checked_add(overlap(present, *margin)?)
+                .ok_or(WRAPPING_OVERFLOW),
+            WrappingRule::GiftBag { sizes } => match smallest_bag(sizes, present)? {
+                Some(bag) => bag_material(bag),
+                None => present.calculate_wrapping_amount(),
+            },
+        }
+    }
+
+    fn ribbon(&self, present: &Present) -> Result<Length, ArithmeticOverflow> {
+        match self.ribbon {
+            RibbonRule::VolumeBow => present.calculate_ribbon_amount(),
+            RibbonRule::LongestSideBow { factor } => present
+                .ribbon_perimeter()?
+                .checked_add(longest_side_bow(present, factor)?)
+                .ok_or(RIBBON_OVERFLOW),
+            RibbonRule::None => Ok(Length::default()),
+        }
+    }
+
+    fn slack(&self, present: &Present) -> Result<Option<Area>, ArithmeticOverflow> {
+        match &self.wrapping {
+            WrappingRule::SmallestSide => present.slack().map(Some),
+            WrappingRule::Overlap { margin } => overlap(present, *margin).map(Some),
+            // Bags aren't cut from the present's sides
+            WrappingRule::GiftBag { sizes } => match smallest_bag(sizes, present)? {
+                Some(_) => Ok(None),
+                None => present.slack().map(Some),
+            },
+        }
+    }
+
+    fn bow(&self, present: &Present) -> Result<Option<Length>, ArithmeticOverflow> {
+        match self.ribbon {
+            RibbonRule::VolumeBow => present.bow().map(Some),
+            RibbonRule::LongestSideBow { factor } => longest_side_bow(present, factor).map(Some),
+            RibbonRule::None => Ok(None),
+        }
+    }
+
+    fn paper_pieces(&self, present: &Present) -> Result<Vec<(Length, Length)>, ArithmeticOverflow> {
+        match &self.wrapping {
+            WrappingRule::SmallestSide => Ok(present.rectangles().to_vec()),
+            WrappingRule::Overlap { margin } => {
+                // Every side, without the slack, and the overlapping strip
+                let mut pieces = present.rectangles()[..6].to_vec();
+                pieces.push((*margin, present.ribbon_perimeter()?));
+                Ok(pieces)
+            }
+            WrappingRule::GiftBag { sizes } => match smallest_bag(sizes, present)? {
+                Some([width, depth, height]) => Ok(vec![
+                    (width, height),
+                    (width, height),
+                    (depth, height),
+                    (depth, height),
+                    (width, depth),
+                ]),
+                None => Ok(present.rectangles().to_vec()),
+            },
+        }
+    }
+
+    fn ribbon_pieces(&self, present: &Present) -> Result<Vec<Length>, ArithmeticOverflow> {
+        match self.ribbon {
+            RibbonRule::VolumeBow => Ok(vec![present.ribbon_perimeter()?, present.bow()?]),
+            RibbonRule::LongestSideBow { factor } => Ok(vec![
+                present.ribbon_perimeter()?,
+                longest_side_bow(present, factor)?,
+            ]),
+            RibbonRule::None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// The bag taking the least material among those the present fits in, if any
+fn smallest_bag(
+    sizes: &[[Length; 3]],
+    present: &Present,
+) -> Result<Option<[Length; 3]>, ArithmeticOverflow> {
+    let mut dimensions = present.dimensions();
+    dimensions.sort();
+
+    let mut smallest_bag = None;
+    for &size in sizes {
+        let mut bag = size;
+        bag.sort();
+        if dimensions
+            .iter()
+            .zip(&bag)
+            .any(|(side, bag_side)| side > bag_side)
+        {
+            continue;
+        }
+
+        let material = bag_material(size)?;
+        if smallest_bag.is_none_or(|(smallest, _)| material < smallest) {
+            smallest_bag = Some((material, size));
+        }
+    }
+
+    Ok(smallest_bag.map(|(_, size)| size))
+}
+
+/// A strip `margin` wide along the smallest perimeter
+fn overlap(present: &Present, margin: Length) -> Result<Area, ArithmeticOverflow> {
+    Area::from_sides(margin, present.ribbon_perimeter()?).ok_or(WRAPPING_OVERFLOW)
+}
+
+/// A bow `factor` times the longest side
+fn longest_side_bow(present: &Present, factor: f64) -> Result<Length, ArithmeticOverflow> {
+    let longest = present.dimensions().into_iter().max().unwrap_or_default();
+    let bow = (longest.micrometres() as f64 * factor).round();
+    if !bow.is_finite() || bow >= u128::MAX as f64 {
+        return Err(RIBBON_OVERFLOW);
+    }
+
+    Ok(Length::from_micrometres(bow as u128))
+}
+
+/// Area of a bag's base and four sides, with no top
+fn bag_material([width, depth, height]: [Length; 3]) -> Result<Area, ArithmeticOverflow> {
+    let side = |a, b| Area::from_sides(a, b).ok_or(WRAPPING_OVERFLOW);
+
+    side(width, height)?
+        .checked_add(side(depth, height)?)
+        .and_then(|sides| sides.checked_mul(2))
+        .and_then(|sides| sides.checked_add(side(width, depth).ok()?))
+        .ok_or(WRAPPING_OVERFLOW)
+}
@@ -0,0 +1,249 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Unit {
+    Millimetre,
+    Centimetre,
+    Metre,
+    Inch,
+    Foot,
+    Yard,
+}
+
+impl Unit {
+    /// Every unit is a whole number of micrometres, so lengths in mixed units add up exactly
+    pub fn micrometres(self) -> u128 {
+        match self {
+            Unit::Millimetre => 1_000,
+            Unit::Centimetre => 10_000,
+            Unit::Metre => 1_000_000,
+            Unit::Inch => 25_400,
+            Unit::Foot => 304_800,
+            Unit::Yard => 914_400,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Millimetre => "mm",
+            Unit::Centimetre => "cm",
+            Unit::Metre => "m",
+            Unit::Inch => "in",
+            Unit::Foot => "ft",
+            Unit::Yard => "yd",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownUnit(pub String);
+
+impl fmt::Display for UnknownUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown unit {:?}, expected one of mm, cm, m, in, ft or yd",
+            self.0
+        )
+    }
+}
+
+impl Error for UnknownUnit {}
+
+impl FromStr for Unit {
+    type Err = UnknownUnit;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mm" => Ok(Unit::Millimetre),
+            "cm" => Ok(Unit::Centimetre),
+            "m" => Ok(Unit::Metre),
+            "in" => Ok(Unit::Inch),
+            "ft" => Ok(Unit::Foot),
+            "yd" => Ok(Unit::Yard),
+            _ => Err(UnknownUnit(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// A computation whose result doesn't fit in 128 bits
+#[derive(Clone, Copy, Debug)]
+pub struct ArithmeticOverflow {
+    pub quantity: &'static str,
+}
+
+impl fmt::Display for ArithmeticOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Arithmetic overflow computing the {}", self.quantity)
+    }
+}
+
+impl Error for ArithmeticOverflow {}
+
+impl From<ArithmeticOverflow> for io::Error {
+    fn from(error: ArithmeticOverflow) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// An exact length, stored in micrometres
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct Length(u128);
+
+impl Length {
+    pub fn new(value: u128, unit: Unit) -> Option<Self> {
+        value.checked_mul(unit.micrometres()).map(Length)
+    }
+
+    pub fn feet(value: usize) -> Self {
+        // A usize of feet always fits in 128 bits of micrometres
+        Length(value as u128 * Unit::Foot.micrometres())
+    }
+
+    pub fn from_micrometres(micrometres: u128) -> Self {
+        Length(micrometres)
+    }
+
+    pub fn micrometres(self) -> u128 {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Length)
+    }
+
+    pub fn checked_mul(self, factor: u128) -> Option<Self> {
+        self.0.checked_mul(factor).map(Length)
+    }
+
+    pub fn in_unit(self, unit: Unit) -> f64 {
+        self.0 as f64 / unit.micrometres() as f64
+    }
+
+    /// Formats the length in the given unit, with up to 3 decimals
+    pub fn display_in(self, unit: Unit) -> impl fmt::Display {
+        Decimal {
+            value: self.0,
+            divisor: unit.micrometres(),
+        }
+    }
+}
+
+/// Lengths in configuration and input files are strings like "3in", or numbers of feet
+impl<'de> Deserialize<'de> for Length {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LengthVisitor;
+
+        impl de::Visitor<'_> for LengthVisitor {
+            type Value = Length;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a length like \"3in\", or a number of feet")
+            }
+
+            fn visit_u64<E: de::Error>(self, feet: u64) -> Result<Length, E> {
+                self.visit_str(&feet.to_string())
+            }
+
+            fn visit_i64<E: de::Error>(self, feet: i64) -> Result<Length, E> {
+                self.visit_str(&feet.to_string())
+            }
+
+            fn visit_str<E: de::Error>(self, length: &str) -> Result<Length, E> {
+                length.trim().parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(LengthVisitor)
+    }
+}
+
+/// An exact area, stored in square micrometres
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct Area(u128);
+
+impl Area {
+    pub fn from_sides(a: Length, b: Length) -> Option<Self> {
+        a.0.checked_mul(b.0).map(Area)
+    }
+
+    pub fn square_micrometres(self) -> u128 {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Area)
+    }
+
+    pub fn checked_mul(self, factor: u128) -> Option<Self> {
+        self.0.checked_mul(factor).map(Area)
+    }
+
+    pub fn in_unit(self, unit: Unit) -> f64 {
+        self.0 as f64 / (unit.micrometres() * unit.micrometres()) as f64
+    }
+
+    /// Formats the area in square units of the given unit, with up to 3 decimals
+    pub fn display_in(self, unit: Unit) -> impl fmt::Display {
+        Decimal {
+            value: self.0,
+            divisor: unit.micrometres() * unit.micrometres(),
+        }
+    }
+}
+
+/// An exact volume, stored in cubic micrometres
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct Volume(u128);
+
+impl Volume {
+    pub fn from_sides(a: Length, b: Length, c: Length) -> Option<Self> {
+        a.0.checked_mul(b.0)
+            .and_then(|base| base.checked_mul(c.0))
+            .map(Volume)
+    }
+
+    pub fn cubic_micrometres(self) -> u128 {
+        self.0
+    }
+
+    pub fn in_unit(self, unit: Unit) -> f64 {
+        self.0 as f64 / (unit.micrometres() as f64).powi(3)
+    }
+}
+
+/// `value / divisor`, rounded to 3 decimals with trailing zeros dropped
+struct Decimal {
+    value: u128,
+    divisor: u128,
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut whole = self.value / self.divisor;
+        let remainder = self.value % self.divisor;
+        let mut thousandths = (remainder * 1000 + self.divisor / 2) / self.divisor;
+        if thousandths == 1000 {
+            whole += 1;
+            thousandths = 0;
+        }
+
+        if thousandths == 0 {
+            write!(f, "{}", whole)
+        } else {
+            let decimals = format!("{:03}", thousandths);
+            write!(f, "{}.{}", whole, decimals.trim_end_matches('0'))
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use aoc2015_day03::Direction;
+use common::xorshift::XorShift;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Builds a random walk, which stays dense enough for the bitset, or a walk drifting away
+/// from the start, sparse enough to fall back to the hash set
+fn generate_input(len: usize, drift: bool) -> Vec<Direction> {
+    XorShift::new(0x2015)
+        .take(len)
+        .enumerate()
+        .map(|(i, random)| match random % 4 {
+            _ if drift && i % 2 == 0 => Direction::East,
+            0 => Direction::North,
+            1 => Direction::East,
+            2 => Direction::South,
+            _ => Direction::West,
+        })
+        .collect()
+}
+
+fn bench_houses(c: &mut Criterion) {
+    let mut group = c.benchmark_group("houses");
+    // Hash sets of millions of houses take a while to build
+    group.sample_size(10);
+    for (name, drift) in [("walk", false), ("drift", true)] {
+        for len in [1 << 16, 1 << 20] {
+            let directions = generate_input(len, drift);
+            assert_eq!(
+                aoc2015_day03::solve(&directions),
+                aoc2015_day03::solve_dense(&directions)
+            );
+
+            group.throughput(Throughput::Elements(len as u64));
+            group.bench_with_input(
+                BenchmarkId::new(format!("hash_set/{}", name), len),
+                &directions,
+                |b, directions| b.iter(|| aoc2015_day03::solve(directions)),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("dense/{}", name), len),
+                &directions,
+                |b, directions| b.iter(|| aoc2015_day03::solve_dense(directions)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_houses);
+criterion_main!(benches);
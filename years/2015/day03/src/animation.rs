@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::RouteStep;
+
+/// Most frames drawn per second, faster routes take several moves per frame
+const MAX_FRAMES_PER_SECOND: f64 = 30.0;
+
+/// ANSI colours the deliverers' trails cycle through
+const COLOURS: [u8; 6] = [31, 32, 33, 34, 35, 36];
+
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationOptions {
+    pub moves_per_second: f64,
+    /// Size of the view in characters, without the status line
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Draws the route in the terminal as it's followed, every deliverer's trail in its own colour
+///
+/// The view always fits the bounding box of the houses visited so far, so once it grows
+/// larger than the terminal every character stands for several houses, coloured after the
+/// last deliverer to visit any of them. Only the first two coordinates are drawn.
+///
+/// Fails with `InvalidInput` before drawing anything unless `moves_per_second` is positive, and
+/// large enough for a frame to last a representable time.
+pub fn animate<I: IntoIterator<Item = RouteStep>, W: Write>(
+    route: I,
+    deliverers: usize,
+    options: AnimationOptions,
+    mut out: W,
+) -> io::Result<()> {
+    let speed = options.moves_per_second;
+    let invalid_speed = |message| io::Error::new(io::ErrorKind::InvalidInput, message);
+    if speed.is_nan() || speed <= 0.0 {
+        return Err(invalid_speed(format!(
+            "Moves per second must be positive, got {}",
+            speed
+        )));
+    }
+
+    let frames_per_second = speed.min(MAX_FRAMES_PER_SECOND);
+    let moves_per_frame = speed / frames_per_second;
+    let frame_time = Duration::try_from_secs_f64(1.0 / frames_per_second).map_err(|_| {
+        invalid_speed(format!(
+            "{:e} moves per second is too slow to animate",
+            speed
+        ))
+    })?;
+
+    // The step every house was last visited on, and by whom
+    let mut trail = HashMap::from([((0, 0), (0, 0))]);
+    let mut positions = vec![(0, 0); deliverers];
+    let (mut min, mut max) = ((0, 0), (0, 0));
+    let mut last_step = None;
+    let mut pending = 0.0;
+
+    // Hide the cursor and clear the screen
+    write!(out, "\x1b[?25l\x1b[2J")?;
+    for step in route {
+        let (x, y, _) = step.position;
+        trail.insert((x, y), (step.step, step.deliverer));
+        positions[step.deliverer] = (x, y);
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+        last_step = Some(step);
+
+        pending += 1.0;
+        if pending >= moves_per_frame {
+            pending -= moves_per_frame;
+            draw(&mut out, &trail, &positions, (min, max), last_step, options)?;
+            thread::sleep(frame_time);
+        }
+    }
+    draw(&mut out, &trail, &positions, (min, max), last_step, options)?;
+
+    // Show the cursor again
+    writeln!(out, "\x1b[?25h")
+}
+
+fn draw<W: Write>(
+    out: &mut W,
+    trail: &HashMap<(isize, isize), (usize, usize)>,
+    positions: &[(isize, isize)],
+    (min, max): ((isize, isize), (isize, isize)),
+    last_step: Option<RouteStep>,
+    options: AnimationOptions,
+) -> io::Result<()> {
+    let width = max.0.abs_diff(min.0) + 1;
+    let height = max.1.abs_diff(min.1) + 1;
+
+    // Houses per character, the same both ways so the route isn't stretched
+    let scale = width
+        .div_ceil(options.width.max(1))
+        .max(height.div_ceil(options.height.max(1)));
+    let columns = width.div_ceil(scale);
+    let rows = height.div_ceil(scale);
+
+    // North is up, so rows count down from the top of the box
+    let cell = |(x, y): (isize, isize)| (max.1.abs_diff(y) / scale, x.abs_diff(min.0) / scale);
+    // Keep the latest visit of every character's houses, whatever order the trail is in
+    let mut latest = vec![vec![None; columns]; rows];
+    for (&house, &visit) in trail {
+        let (row, column) = cell(house);
+        let latest = &mut latest[row][column];
+        if latest.is_none_or(|(step, _)| step < visit.0) {
+            *latest = Some(visit);
+        }
+    }
+    let mut view: Vec<Vec<_>> = latest
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|visit| visit.map(|(_, deliverer)| (deliverer, false)))
+                .collect()
+        })
+        .collect();
+    for (deliverer, &position) in positions.iter().enumerate() {
+        let (row, column) = cell(position);
+        view[row][column] = Some((deliverer, true));
+    }
+
+    write!(out, "\x1b[H")?;
+    if let Some(step) = last_step {
+        let (x, y, _) = step.position;
+        write!(
+            out,
+            "Step {}: deliverer {} at ({}, {}){}, {} houses per character\x1b[K\r\n",
+            step.step,
+            step.deliverer + 1,
+            x,
+            y,
+            if step.newly_visited {
+                ", new house"
+            } else {
+                ""
+            },
+            scale * scale
+        )?;
+    }
+    for row in view {
+        for cell in row {
+            match cell {
+                Some((deliverer, current)) => write!(
+                    out,
+                    "\x1b[{}m{}\x1b[0m",
+                    COLOURS[deliverer % COLOURS.len()],
+                    if current { '@' } else { '#' }
+                )?,
+                None => write!(out, " ")?,
+            }
+        }
+        write!(out, "\x1b[K\r\n")?;
+    }
+    // Clear what's left of a larger previous frame
+    write!(out, "\x1b[J")?;
+
+    out.flush()
+}
@@ -0,0 +1,154 @@
+use crate::{Direction, Fleet, Move};
+
+/// Most bits of grid per move before the path counts as too sparse for one, so the grid never
+/// takes more than 8 bytes per move
+const MAX_BITS_PER_MOVE: usize = 64;
+
+/// Cardinal moves stay on the ground, so only `x` and `y` are tracked
+fn step((x, y): (isize, isize), direction: &Direction) -> (isize, isize) {
+    let step = Move::from(direction);
+    (x + step.x, y + step.y)
+}
+
+/// Smallest and largest corner of a rectangle of houses
+type Bounds = ((isize, isize), (isize, isize));
+
+/// Smallest rectangles containing every house visited by Santa alone, and by Santa and
+/// Robo-Santa taking turns, both found in a single pass
+fn bounding_boxes(directions: &[Direction]) -> [Bounds; 2] {
+    let widen = |(min, max): &mut Bounds, position: (isize, isize)| {
+        *min = (min.0.min(position.0), min.1.min(position.1));
+        *max = (max.0.max(position.0), max.1.max(position.1));
+    };
+
+    let mut alone = (0, 0);
+    let mut turns = [(0, 0); 2];
+    let mut boxes = [((0, 0), (0, 0)); 2];
+    for (i, direction) in directions.iter().enumerate() {
+        alone = step(alone, direction);
+        widen(&mut boxes[0], alone);
+
+        turns[i % 2] = step(turns[i % 2], direction);
+        widen(&mut boxes[1], turns[i % 2]);
+    }
+
+    boxes
+}
+
+/// Counts the houses visited by `deliverers` Santas taking turns, marking them in a bitset
+/// over their bounding box, or `None` if the box is too sparse for one
+fn count_dense(directions: &[Direction], deliverers: usize, (min, max): Bounds) -> Option<usize> {
+    let width = max.0.abs_diff(min.0) + 1;
+    let height = max.1.abs_diff(min.1) + 1;
+    let area = width.checked_mul(height)?;
+    if area > MAX_BITS_PER_MOVE.saturating_mul(directions.len() + 1) {
+        return None;
+    }
+
+    let mut grid = vec![0u64; area.div_ceil(64)];
+    let mut visit = |(x, y): (isize, isize)| {
+        let bit = y.abs_diff(min.1) * width + x.abs_diff(min.0);
+        let mask = 1 << (bit % 64);
+        let word = &mut grid[bit / 64];
+        let new = *word & mask == 0;
+        *word |= mask;
+
+        new as usize
+    };
+
+    let mut positions = vec![(0, 0); deliverers];
+    let mut houses = visit((0, 0));
+    for (i, direction) in directions.iter().enumerate() {
+        let position = step(positions[i % deliverers], direction);
+        positions[i % deliverers] = position;
+
+        houses += visit(position);
+    }
+
+    Some(houses)
+}
+
+/// Counts the houses visited by `deliverers` Santas taking turns in a hash set, as `solve` does
+fn count_sparse(directions: &[Direction], deliverers: usize) -> usize {
+    let mut fleet = Fleet::round_robin(deliverers);
+    for direction in directions {
+        fleet.move_sleigh(direction);
+    }
+
+    fleet.houses_visited()
+}
+
+/// Same as `solve`, but with the visited houses in a dense bitset over the path's bounding box
+/// instead of a hash set, found with a first pass over the directions
+///
+/// Each part falls back to a hash set on its own when its bounding box is too sparse for a
+/// bitset to pay off.
+pub fn solve_dense(directions: &[Direction]) -> (usize, usize) {
+    let [alone, turns] = bounding_boxes(directions);
+    let count = |deliverers, bounds| {
+        count_dense(directions, deliverers, bounds)
+            .unwrap_or_else(|| count_sparse(directions, deliverers))
+    };
+
+    (count(1, alone), count(2, turns))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::xorshift::XorShift;
+
+    use super::*;
+
+    /// A random walk, drifting east on every other move if `drift` is set
+    fn random_walk(len: usize, seed: u64, drift: bool) -> Vec<Direction> {
+        XorShift::new(seed)
+            .take(len)
+            .enumerate()
+            .map(|(i, random)| match random % 4 {
+                _ if drift && i % 2 == 0 => Direction::East,
+                0 => Direction::North,
+                1 => Direction::East,
+                2 => Direction::South,
+                _ => Direction::West,
+            })
+            .collect()
+    }
+
+    /// Whether each part is counted in a bitset
+    fn dense_parts(directions: &[Direction]) -> [bool; 2] {
+        let [alone, turns] = bounding_boxes(directions);
+        [
+            count_dense(directions, 1, alone).is_some(),
+            count_dense(directions, 2, turns).is_some(),
+        ]
+    }
+
+    #[test]
+    fn matches_solve_on_dense_walks() {
+        for (len, seed) in [(0, 1), (1, 2), (2, 3), (1000, 4), (1 << 16, 5)] {
+            let directions = random_walk(len, seed, false);
+            assert_eq!(dense_parts(&directions), [true, true]);
+            assert_eq!(solve_dense(&directions), crate::solve(&directions));
+        }
+    }
+
+    #[test]
+    fn matches_solve_when_falling_back() {
+        for (len, seed) in [(1 << 17, 6), (1 << 18, 7)] {
+            let directions = random_walk(len, seed, true);
+            assert_eq!(dense_parts(&directions), [false, false]);
+            assert_eq!(solve_dense(&directions), crate::solve(&directions));
+        }
+    }
+
+    #[test]
+    fn falls_back_for_one_part_only() {
+        // Alone, Santa never leaves the starting square, while taking turns Santa heads north
+        // east and Robo-Santa south west, spanning a box far too sparse for the path
+        let input = "^v".repeat(1000) + &"><".repeat(1000);
+        let directions = crate::parse_reader(input.as_bytes()).unwrap();
+
+        assert_eq!(dense_parts(&directions), [true, false]);
+        assert_eq!(solve_dense(&directions), crate::solve(&directions));
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use crate::moves::split_moves;
+use crate::{Direction, Move, MoveSet, Santa};
+
+/// Which deliverer takes each move
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Dispatch {
+    /// Every deliverer takes one move in turn
+    RoundRobin,
+    /// Every deliverer takes as many consecutive moves as its weight, in turn
+    Weighted(Vec<usize>),
+    /// Deliverers take moves following a repeating pattern of their indices
+    Pattern(Vec<usize>),
+    /// The same deliverer takes every move until this character hands over to the next
+    Marker(char),
+}
+
+impl Dispatch {
+    /// Parses a pattern like "SSR", where every distinct letter is a deliverer, in order of appearance
+    pub fn from_pattern(pattern: &str) -> Self {
+        let mut names = Vec::new();
+        let indices = pattern
+            .chars()
+            .map(|name| match names.iter().position(|&n| n == name) {
+                Some(index) => index,
+                None => {
+                    names.push(name);
+                    names.len() - 1
+                }
+            })
+            .collect();
+
+        Dispatch::Pattern(indices)
+    }
+
+    /// Number of deliverers the policy names, if it does
+    pub fn implied_santas(&self) -> Option<usize> {
+        match self {
+            Dispatch::Weighted(weights) => Some(weights.len()),
+            Dispatch::Pattern(indices) => indices.iter().max().map(|&max| max + 1),
+            Dispatch::RoundRobin | Dispatch::Marker(_) => None,
+        }
+    }
+}
+
+/// Parses "round-robin", "weighted:2,1", "pattern:SSR" or "marker:*"
+impl FromStr for Dispatch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "round-robin" => Ok(Dispatch::RoundRobin),
+            Some(("weighted", weights)) => weights
+                .split(',')
+                .map(|weight| weight.trim().parse())
+                .collect::<Result<_, _>>()
+                .map(Dispatch::Weighted)
+                .map_err(|e| format!("Invalid weight: {}", e)),
+            Some(("pattern", pattern)) => Ok(Dispatch::from_pattern(pattern)),
+            Some(("marker", marker)) => {
+                let mut chars = marker.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(marker), None) => Ok(Dispatch::Marker(marker)),
+                    _ => Err(format!("Expected a single marker character, got {:?}", marker)),
+                }
+            }
+            _ => Err(format!(
+                "Unknown dispatch {:?}, expected round-robin, weighted:<weights>, pattern:<letters> or marker:<character>",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidFleet {
+    pub reason: String,
+}
+
+impl fmt::Display for InvalidFleet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid fleet: {}", self.reason)
+    }
+}
+
+impl Error for InvalidFleet {}
+
+/// Houses visited by every deliverer, and how they overlap
+#[derive(Debug)]
+pub struct Coverage {
+    /// Number of houses visited by each deliverer
+    pub unique_houses: Vec<usize>,
+    /// Number of houses visited by any deliverer
+    pub union: usize,
+    /// Number of houses visited by more than one deliverer
+    pub overlaps: usize,
+    /// Number of houses visited by both deliverers, as `(i, j, houses)` for every pair `i < j`
+    pub pairwise: Vec<(usize, usize, usize)>,
+}
+
+/// Any number of Santas sharing the directions following a dispatch policy
+pub struct Fleet {
+    santas: Vec<Santa>,
+    dispatch: Dispatch,
+    current: usize,
+    /// Moves taken by the current deliverer, or in total for a pattern
+    moves: usize,
+}
+
+impl Fleet {
+    pub fn new(santas: usize, dispatch: Dispatch) -> Result<Self, InvalidFleet> {
+        let invalid = |reason: String| Err(InvalidFleet { reason });
+
+        if santas == 0 {
+            return invalid("There must be at least one Santa".to_string());
+        }
+        match &dispatch {
+            Dispatch::Weighted(weights) if weights.len() != santas => {
+                return invalid(format!("{} weights for {} Santas", weights.len(), santas));
+            }
+            Dispatch::Weighted(weights) if weights.iter().all(|&weight| weight == 0) => {
+                return invalid("Every weight is zero".to_string());
+            }
+            Dispatch::Pattern(indices) if indices.is_empty() => {
+                return invalid("The pattern is empty".to_string());
+            }
+            Dispatch::Pattern(_) if dispatch.implied_santas() > Some(santas) => {
+                return invalid(format!(
+                    "The pattern names {} Santas, but there are only {}",
+                    dispatch.implied_santas().unwrap_or_default(),
+                    santas
+                ));
+            }
+            _ => {}
+        }
+
+        let mut fleet = Fleet {
+            santas: (0..santas).map(|_| Santa::new()).collect(),
+            dispatch,
+            current: 0,
+            moves: 0,
+        };
+        fleet.skip_idle();
+
+        Ok(fleet)
+    }
+
+    /// A fleet of Santas taking turns, like Santa and Robo-Santa
+    ///
+    /// # Panics
+    ///
+    /// If `santas` is 0.
+    pub fn round_robin(santas: usize) -> Self {
+        assert!(santas > 0, "There must be at least one Santa");
+
+        Fleet {
+            santas: (0..santas).map(|_| Santa::new()).collect(),
+            dispatch: Dispatch::RoundRobin,
+            current: 0,
+            moves: 0,
+        }
+    }
+
+    pub fn santas(&self) -> &[Santa] {
+        &self.santas
+    }
+
+    /// Index of the deliverer taking the next move
+    pub fn current(&self) -> usize {
+        match &self.dispatch {
+            Dispatch::Pattern(indices) => indices[self.moves % indices.len()],
+            _ => self.current,
+        }
+    }
+
+    /// Has the deliverer whose turn it is take the move, then dispatches the next one
+    pub fn move_sleigh(&mut self, direction: &Direction) {
+        self.move_by(Move::from(direction));
+    }
+
+    /// Same as `move_sleigh`, for a move of any move set, returning whether the deliverer
+    /// hadn't visited the house yet
+    pub fn move_by(&mut self, step: Move) -> bool {
+        let current = self.current();
+        let newly_visited = self.santas[current].move_by(step);
+
+        self.moves += 1;
+        match &self.dispatch {
+            Dispatch::RoundRobin => self.hand_over(),
+            Dispatch::Weighted(weights) if self.moves >= weights[self.current] => {
+                self.hand_over();
+                self.skip_idle();
+            }
+            Dispatch::Weighted(_) | Dispatch::Pattern(_) | Dispatch::Marker(_) => {}
+        }
+
+        newly_visited
+    }
+
+    /// Follows the instructions in the move set, optionally run-length encoded, handing over to
+    /// the next deliverer on the marker, if the policy has one, and skipping anything else that
+    /// isn't a move
+    pub fn deliver(
+        &mut self,
+        instructions: &str,
+        set: MoveSet,
+        run_length: bool,
+    ) -> io::Result<()> {
+        let marker = match self.dispatch {
+            Dispatch::Marker(marker) => Some(marker),
+            _ => None,
+        };
+
+        let parts = split_moves(instructions, set, run_length, marker)?;
+        for (i, moves) in parts.into_iter().enumerate() {
+            if i > 0 {
+                self.hand_over();
+            }
+            for (step, count) in moves {
+                for _ in 0..count {
+                    self.move_by(step);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of houses visited by any deliverer
+    pub fn houses_visited(&self) -> usize {
+        match &self.santas[..] {
+            [santa] => santa.visited_houses.len(),
+            santas => santas
+                .iter()
+                .flat_map(|santa| &santa.visited_houses)
+                .collect::<HashSet<_>>()
+                .len(),
+        }
+    }
+
+    pub fn coverage(&self) -> Coverage {
+        let mut deliverers: HashMap<_, usize> = HashMap::new();
+        for santa in &self.santas {
+            for house in &santa.visited_houses {
+                *deliverers.entry(house).or_default() += 1;
+            }
+        }
+
+        let mut pairwise = Vec::new();
+        for (i, first) in self.santas.iter().enumerate() {
+            for (j, second) in self.santas.iter().enumerate().skip(i + 1) {
+                let shared = first
+                    .visited_houses
+                    .intersection(&second.visited_houses)
+                    .count();
+                pairwise.push((i, j, shared));
+            }
+        }
+
+        Coverage {
+            unique_houses: self
+                .santas
+                .iter()
+                .map(|santa| santa.visited_houses.len())
+                .collect(),
+            union: deliverers.len(),
+            overlaps: deliverers.values().filter(|&&count| count > 1).count(),
+            pairwise,
+        }
+    }
+
+    fn hand_over(&mut self) {
+        self.current = (self.current + 1) % self.santas.len();
+        self.moves = 0;
+    }
+
+    /// Skips the deliverers weighted zero
+    fn skip_idle(&mut self) {
+        if let Dispatch::Weighted(weights) = &self.dispatch {
+            while weights[self.current] == 0 {
+                self.current = (self.current + 1) % weights.len();
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+mod animation;
+mod dense;
+mod fleet;
+mod moves;
+mod planner;
+mod route;
+mod stats;
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+use common::check::{self, Problem};
+use common::{Answer, Report};
+
+pub use animation::{animate, AnimationOptions};
+pub use dense::solve_dense;
+pub use fleet::{Coverage, Dispatch, Fleet, InvalidFleet};
+pub use moves::{parse_moves, solve_moves, Move, MoveSet};
+pub use planner::{parse_houses, plan, Plan};
+pub use route::RouteStep;
+pub use stats::{BoundingBox, CountingSanta, DeliveryStatistics};
+
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// The direction a single symbol stands for, if any, without allocating a string
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(symbol), None) => {
+                Direction::from_symbol(symbol).ok_or("Invalid direction string")
+            }
+            _ => Err("Invalid direction string"),
+        }
+    }
+}
+
+/// Houses are on a 3D grid so every move set fits, flat ones staying at `z = 0`
+pub type House = (isize, isize, isize);
+
+pub struct Santa {
+    position: House,
+    visited_houses: HashSet<House>,
+}
+
+impl Default for Santa {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Santa {
+    pub fn new() -> Self {
+        Santa {
+            position: (0, 0, 0),
+            visited_houses: HashSet::from([(0, 0, 0)]),
+        }
+    }
+
+    pub fn move_sleigh(&mut self, direction: &Direction) {
+        self.move_by(Move::from(direction));
+    }
+
+    /// Returns whether the house hadn't been visited yet
+    pub fn move_by(&mut self, step: Move) -> bool {
+        let (x, y, z) = self.position;
+        self.position = (x + step.x, y + step.y, z + step.z);
+
+        self.visited_houses.insert(self.position)
+    }
+
+    pub fn position(&self) -> House {
+        self.position
+    }
+
+    pub fn visited_houses(&self) -> &HashSet<House> {
+        &self.visited_houses
+    }
+}
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<Vec<Direction>> {
+    // Open input file
+    let input = File::open(file_path)?;
+
+    parse_reader(input)
+}
+
+pub fn parse_reader<R: Read>(reader: R) -> io::Result<Vec<Direction>> {
+    parse_stream(reader).collect()
+}
+
+/// Lazily reads the directions one at a time, so inputs of any size take constant memory
+///
+/// Like `parse_reader`, anything that isn't a direction is skipped.
+pub fn parse_stream<R: Read>(reader: R) -> impl Iterator<Item = io::Result<Direction>> {
+    BufReader::new(reader)
+        .bytes()
+        .filter_map(|byte| match byte {
+            Ok(byte) => Direction::from_symbol(char::from(byte)).map(Ok),
+            Err(e) => Some(Err(e)),
+        })
+}
+
+/// Reports every character that isn't a direction, without solving
+///
+/// The parser silently skips these, so they would go unnoticed otherwise.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = check::check_line_endings(input);
+
+    let line_count = input.lines().count();
+    for (i, line) in input.lines().enumerate() {
+        for (j, c) in line.trim_end().chars().enumerate() {
+            if Direction::from_symbol(c).is_none() {
+                problems.push(Problem::new(
+                    i + 1,
+                    j + 1,
+                    format!("Invalid direction {:?}", c),
+                ));
+            }
+        }
+
+        if i + 1 < line_count {
+            problems.push(Problem::new(
+                i + 1,
+                line.chars().count() + 1,
+                "Unexpected line break",
+            ));
+        }
+    }
+
+    problems
+}
+
+/// Returns the number of houses visited by Santa alone, and by Santa and Robo-Santa together
+pub fn solve(directions: &[Direction]) -> (usize, usize) {
+    let mut santa = Fleet::round_robin(1);
+    let mut santa_and_robo_santa = Fleet::round_robin(2);
+    for direction in directions {
+        santa.move_sleigh(direction);
+        santa_and_robo_santa.move_sleigh(direction);
+    }
+
+    (
+        santa.houses_visited(),
+        santa_and_robo_santa.houses_visited(),
+    )
+}
+
+/// Same as `solve`, but over a stream of directions, stopping at the first error
+///
+/// Only the visited houses are kept in memory, not the directions.
+pub fn solve_stream<I: IntoIterator<Item = io::Result<Direction>>>(
+    directions: I,
+) -> io::Result<(usize, usize)> {
+    let mut santa = Fleet::round_robin(1);
+    let mut santa_and_robo_santa = Fleet::round_robin(2);
+    for direction in directions {
+        let direction = direction?;
+
+        santa.move_sleigh(&direction);
+        santa_and_robo_santa.move_sleigh(&direction);
+    }
+
+    Ok((
+        santa.houses_visited(),
+        santa_and_robo_santa.houses_visited(),
+    ))
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let directions = parse_input(file_path)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (num_visited_houses_1, num_visited_houses_2) = solve(&directions);
+    let solution_time = t1.elapsed();
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new(
+                "Number of houses visited by one Santa",
+                num_visited_houses_1,
+            ),
+            Answer::new(
+                "Number of houses visited by Santa and Robo-Santa",
+                num_visited_houses_2,
+            ),
+        ],
+    })
+}
+
+/// Same as `run`, following the symbols of another move set, optionally run-length encoded
+pub fn run_with_moves<T: AsRef<Path>>(
+    file_path: T,
+    set: MoveSet,
+    run_length: bool,
+) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let moves = parse_moves(&fs::read_to_string(file_path)?, set, run_length)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (num_visited_houses_1, num_visited_houses_2) = solve_moves(&moves);
+    let solution_time = t1.elapsed();
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new(
+                "Number of houses visited by one Santa",
+                num_visited_houses_1,
+            ),
+            Answer::new(
+                "Number of houses visited by Santa and Robo-Santa",
+                num_visited_houses_2,
+            ),
+        ],
+    })
+}
@@ -0,0 +1,237 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io, iter};
+
+use aoc2015_day03::{AnimationOptions, BoundingBox, CountingSanta, Dispatch, Fleet, MoveSet};
+use clap::{ArgGroup, Parser};
+
+#[derive(Parser)]
+#[command(group(ArgGroup::new("fleet").args(["dispatch", "plan"]).multiple(true)))]
+struct Cli {
+    /// Input file
+    #[arg(long, default_value = "years/2015/day03/input")]
+    input: PathBuf,
+
+    /// Also deliver with a fleet following this policy: round-robin, weighted:<weights>,
+    /// pattern:<letters> (like pattern:SSR) or marker:<character>
+    #[arg(long)]
+    dispatch: Option<Dispatch>,
+
+    /// Symbols to follow: cardinal, eight-way, hex or 3d
+    #[arg(long, default_value = "cardinal")]
+    moves: MoveSet,
+
+    /// Read a repeat count after every move, like ^5>3
+    #[arg(long)]
+    run_length: bool,
+
+    /// Also count the presents every house gets from Santa alone, and report statistics
+    #[arg(long)]
+    stats: bool,
+
+    /// Number of Santas in the fleet, by default as many as the policy names, or 2, or 1 when
+    /// planning
+    #[arg(long, requires = "fleet")]
+    santas: Option<usize>,
+
+    /// Instead of solving, play the route back in the terminal, following --dispatch or
+    /// taking turns with Robo-Santa
+    #[arg(long, conflicts_with = "stats")]
+    animate: bool,
+
+    /// Moves drawn per second when animating
+    #[arg(long, default_value_t = 20.0, requires = "animate")]
+    speed: f64,
+
+    /// Instead of solving, plan directions visiting every house in this file, one per line as
+    /// x,y, split between --santas
+    #[arg(long, conflicts_with_all = ["stats", "animate", "dispatch"])]
+    plan: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    if cli.animate {
+        return animate(&cli);
+    }
+    if let Some(houses) = &cli.plan {
+        return plan(houses, cli.santas.unwrap_or(1));
+    }
+
+    let fleet = match cli.dispatch {
+        Some(dispatch) => {
+            let santas = cli.santas.or(dispatch.implied_santas()).unwrap_or(2);
+            Some(Fleet::new(santas, dispatch)?)
+        }
+        None => None,
+    };
+
+    let report = if cli.moves == MoveSet::Cardinal && !cli.run_length {
+        aoc2015_day03::run(&cli.input)?
+    } else {
+        aoc2015_day03::run_with_moves(&cli.input, cli.moves, cli.run_length)?
+    };
+    report.print();
+
+    // Houses only have a third coordinate worth showing when moving in 3D
+    let house = |(x, y, z)| match cli.moves {
+        MoveSet::ThreeD => format!("({}, {}, {})", x, y, z),
+        _ => format!("({}, {})", x, y),
+    };
+
+    if cli.stats {
+        let mut santa = CountingSanta::new();
+        let moves = aoc2015_day03::parse_moves(
+            &fs::read_to_string(&cli.input)?,
+            cli.moves,
+            cli.run_length,
+        )?;
+        for (step, count) in moves {
+            for _ in 0..count {
+                santa.move_by(step);
+            }
+        }
+        let stats = santa.statistics();
+
+        let (most_visited, presents) = stats.most_visited;
+        println!(
+            "Delivery statistics:\nMost visited house: {}, {} presents",
+            house(most_visited),
+            presents
+        );
+        let distribution: Vec<_> = stats
+            .distribution
+            .iter()
+            .map(|(presents, houses)| format!("{} houses got {}", houses, presents))
+            .collect();
+        println!("Presents per house: {}", distribution.join(", "));
+        match stats.first_revisit {
+            Some((revisited, step)) => {
+                println!(
+                    "First house visited twice: {}, at step {}",
+                    house(revisited),
+                    step
+                )
+            }
+            None => println!("First house visited twice: none"),
+        }
+        match stats.loop_lengths {
+            Some((shortest, longest)) => println!(
+                "Loops: {}, from {} to {} steps long",
+                stats.loops, shortest, longest
+            ),
+            None => println!("Loops: 0"),
+        }
+        let BoundingBox { min, max } = stats.bounding_box;
+        match stats.bounding_box.area().zip(stats.coverage) {
+            Some((area, coverage)) => println!(
+                "Bounding box: {} to {}, {} houses, {:.1}% visited",
+                house(min),
+                house(max),
+                area,
+                coverage * 100.0
+            ),
+            None => println!(
+                "Bounding box: {} to {}, too many houses to count",
+                house(min),
+                house(max)
+            ),
+        }
+    }
+
+    if let Some(mut fleet) = fleet {
+        fleet.deliver(&fs::read_to_string(&cli.input)?, cli.moves, cli.run_length)?;
+
+        let coverage = fleet.coverage();
+        println!("Fleet:");
+        for (i, houses) in coverage.unique_houses.iter().enumerate() {
+            println!("Santa {}: {} houses", i + 1, houses);
+        }
+        println!(
+            "Houses visited by any Santa: {}\nHouses visited by more than one Santa: {}",
+            coverage.union, coverage.overlaps
+        );
+        for (i, j, houses) in coverage.pairwise {
+            println!(
+                "Houses visited by both Santa {} and {}: {}",
+                i + 1,
+                j + 1,
+                houses
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn animate(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    let mut fleet = match &cli.dispatch {
+        Some(Dispatch::Marker(_)) => {
+            return Err("--animate follows moves only, it can't hand over on a marker".into());
+        }
+        Some(dispatch) => {
+            let santas = cli.santas.or(dispatch.implied_santas()).unwrap_or(2);
+            Fleet::new(santas, dispatch.clone())?
+        }
+        None => Fleet::round_robin(2),
+    };
+    let moves =
+        aoc2015_day03::parse_moves(&fs::read_to_string(&cli.input)?, cli.moves, cli.run_length)?;
+
+    // Fill the terminal, leaving a line for the status and one for the shell prompt
+    let size = |name, default: usize| {
+        env::var(name)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+    let options = AnimationOptions {
+        moves_per_second: cli.speed,
+        width: size("COLUMNS", 80),
+        height: size("LINES", 24).saturating_sub(2),
+    };
+
+    let deliverers = fleet.santas().len();
+    let route = fleet.route(
+        moves
+            .into_iter()
+            .flat_map(|(step, count)| iter::repeat_n(step, count)),
+    );
+    aoc2015_day03::animate(route, deliverers, options, io::stdout().lock())?;
+
+    Ok(())
+}
+
+fn plan(houses: &Path, santas: usize) -> Result<(), Box<dyn Error>> {
+    if santas == 0 {
+        return Err("There must be at least one Santa".into());
+    }
+
+    let plan = aoc2015_day03::plan(
+        &aoc2015_day03::parse_houses(&fs::read_to_string(houses)?)?,
+        santas,
+    );
+    for (i, directions) in plan.directions().iter().enumerate() {
+        println!(
+            "Santa {}: {} houses, {} moves",
+            i + 1,
+            plan.stops[i].len(),
+            directions.len()
+        );
+    }
+    let above = if plan.lower_bound == 0 {
+        0.0
+    } else {
+        (plan.length as f64 / plan.lower_bound as f64 - 1.0) * 100.0
+    };
+    println!(
+        "Route length: {} moves, lower bound {} ({:.1}% above)",
+        plan.length, plan.lower_bound, above
+    );
+
+    // With several Santas, follow it with --dispatch marker:*
+    println!("{}", plan.instructions('*'));
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::io;
+use std::mem;
+use std::str::FromStr;
+
+use crate::{Direction, Fleet};
+
+/// A move between neighbouring houses
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Move {
+    const fn new(x: isize, y: isize, z: isize) -> Self {
+        Move { x, y, z }
+    }
+}
+
+impl From<&Direction> for Move {
+    fn from(direction: &Direction) -> Self {
+        match direction {
+            Direction::North => Move::new(0, 1, 0),
+            Direction::East => Move::new(1, 0, 0),
+            Direction::South => Move::new(0, -1, 0),
+            Direction::West => Move::new(-1, 0, 0),
+        }
+    }
+}
+
+/// The symbols a Santa follows, and where they take him
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum MoveSet {
+    /// `^>v<`
+    #[default]
+    Cardinal,
+    /// `^>v<`, and the keyboard corners around 's' for the diagonals: `q` north-west,
+    /// `e` north-east, `z` south-west and `c` south-east
+    EightWay,
+    /// Flat-topped hexagons in axial coordinates, with north as +y like the other sets: `^` and
+    /// `v` for north and south, and the same corners as `EightWay` for the other four sides
+    Hex,
+    /// `^>v<`, and `+` and `-` for up and down
+    ThreeD,
+}
+
+impl MoveSet {
+    pub fn parse_move(self, symbol: char) -> Option<Move> {
+        let cardinal = || Direction::from_symbol(symbol).map(|d| Move::from(&d));
+
+        match (self, symbol) {
+            (MoveSet::Cardinal, _) => cardinal(),
+            (MoveSet::EightWay, 'q') => Some(Move::new(-1, 1, 0)),
+            (MoveSet::EightWay, 'e') => Some(Move::new(1, 1, 0)),
+            (MoveSet::EightWay, 'z') => Some(Move::new(-1, -1, 0)),
+            (MoveSet::EightWay, 'c') => Some(Move::new(1, -1, 0)),
+            (MoveSet::EightWay, _) => cardinal(),
+            (MoveSet::Hex, '^' | 'v') => cardinal(),
+            (MoveSet::Hex, 'q') => Some(Move::new(-1, 0, 0)),
+            (MoveSet::Hex, 'e') => Some(Move::new(1, 1, 0)),
+            (MoveSet::Hex, 'z') => Some(Move::new(-1, -1, 0)),
+            (MoveSet::Hex, 'c') => Some(Move::new(1, 0, 0)),
+            (MoveSet::Hex, _) => None,
+            (MoveSet::ThreeD, '+') => Some(Move::new(0, 0, 1)),
+            (MoveSet::ThreeD, '-') => Some(Move::new(0, 0, -1)),
+            (MoveSet::ThreeD, _) => cardinal(),
+        }
+    }
+}
+
+impl FromStr for MoveSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cardinal" => Ok(MoveSet::Cardinal),
+            "eight-way" => Ok(MoveSet::EightWay),
+            "hex" => Ok(MoveSet::Hex),
+            "3d" => Ok(MoveSet::ThreeD),
+            _ => Err(format!(
+                "Unknown move set {:?}, expected one of cardinal, eight-way, hex or 3d",
+                s
+            )),
+        }
+    }
+}
+
+/// Reads the moves of the set, with how many times each is repeated
+///
+/// With `run_length`, a move can be followed by a repeat count, like `^5>3`. Like
+/// `parse_reader`, anything else that isn't a move is skipped.
+pub fn parse_moves(input: &str, set: MoveSet, run_length: bool) -> io::Result<Vec<(Move, usize)>> {
+    let mut parts = split_moves(input, set, run_length, None)?;
+
+    Ok(parts.pop().unwrap_or_default())
+}
+
+/// Same as `parse_moves`, but starting a new list of moves after every `marker`, which is never
+/// read as a move
+pub(crate) fn split_moves(
+    input: &str,
+    set: MoveSet,
+    run_length: bool,
+    marker: Option<char>,
+) -> io::Result<Vec<Vec<(Move, usize)>>> {
+    let mut parts = Vec::new();
+    let mut moves = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, symbol)) = chars.next() {
+        if Some(symbol) == marker {
+            parts.push(mem::take(&mut moves));
+            continue;
+        }
+        let Some(step) = set.parse_move(symbol) else {
+            continue;
+        };
+
+        let mut count = 1;
+        if run_length {
+            let start = i + symbol.len_utf8();
+            let mut end = start;
+            while let Some(&(j, digit)) = chars.peek() {
+                if !digit.is_ascii_digit() {
+                    break;
+                }
+                end = j + 1;
+                chars.next();
+            }
+
+            if end > start {
+                count = input[start..end].parse().map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Repeat count {} at offset {} is too large",
+                            &input[start..end],
+                            start
+                        ),
+                    )
+                })?;
+            }
+        }
+
+        moves.push((step, count));
+    }
+    parts.push(moves);
+
+    Ok(parts)
+}
+
+/// Same as `solve`, for moves of any move set
+pub fn solve_moves(moves: &[(Move, usize)]) -> (usize, usize) {
+    let mut santa = Fleet::round_robin(1);
+    let mut santa_and_robo_santa = Fleet::round_robin(2);
+    for &(step, count) in moves {
+        for _ in 0..count {
+            santa.move_by(step);
+            santa_and_robo_santa.move_by(step);
+        }
+    }
+
+    (
+        santa.houses_visited(),
+        santa_and_robo_santa.houses_visited(),
+    )
+}
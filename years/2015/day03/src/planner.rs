@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+use std::f64::consts::TAU;
+use std::{io, iter, mem};
+
+/// Routes visiting a set of houses from the starting one, one per Santa
+#[derive(Debug)]
+pub struct Plan {
+    /// Houses every Santa visits, in order, without the starting house
+    pub stops: Vec<Vec<(isize, isize)>>,
+    /// Moves taken by all the Santas together
+    pub length: usize,
+    /// Fewest moves any plan could take, the weight of a minimum spanning tree over the houses
+    pub lower_bound: usize,
+}
+
+impl Plan {
+    /// Directions for every Santa, going east or west before north or south between stops
+    pub fn directions(&self) -> Vec<String> {
+        self.stops
+            .iter()
+            .map(|stops| {
+                let mut directions = String::new();
+                let mut position = (0, 0);
+                for &(x, y) in stops {
+                    let east_west = if x > position.0 { '>' } else { '<' };
+                    let north_south = if y > position.1 { '^' } else { 'v' };
+                    directions.extend(iter::repeat_n(east_west, x.abs_diff(position.0)));
+                    directions.extend(iter::repeat_n(north_south, y.abs_diff(position.1)));
+                    position = (x, y);
+                }
+
+                directions
+            })
+            .collect()
+    }
+
+    /// Every Santa's directions, separated by the marker, to follow with `Dispatch::Marker`
+    pub fn instructions(&self, marker: char) -> String {
+        self.directions().join(&marker.to_string())
+    }
+}
+
+/// Reads one house per line as `x,y`, skipping blank lines
+pub fn parse_houses(input: &str) -> io::Result<Vec<(isize, isize)>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.split_once(',')
+                .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Line {}: expected a house as x,y", i + 1),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn distance(a: (isize, isize), b: (isize, isize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// Plans short routes for the Santas, all starting at the origin, that together visit every
+/// house
+///
+/// The houses are shared by sweeping around the origin, cutting at the widest gap so nearby
+/// houses go to the same Santa. Every route is built nearest neighbour first, then improved with
+/// 2-opt until no reversal shortens it.
+///
+/// # Panics
+///
+/// If `santas` is 0.
+pub fn plan(houses: &[(isize, isize)], santas: usize) -> Plan {
+    assert!(santas > 0, "There must be at least one Santa");
+
+    // The starting house already has a present
+    let mut seen = HashSet::from([(0, 0)]);
+    let mut targets: Vec<_> = houses
+        .iter()
+        .copied()
+        .filter(|&house| seen.insert(house))
+        .collect();
+
+    let angle = |&(x, y): &(isize, isize)| (y as f64).atan2(x as f64).rem_euclid(TAU);
+    targets.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+    let angles: Vec<_> = targets.iter().map(angle).collect();
+    let gap =
+        |i: usize| (angles[i] - angles[(i + angles.len() - 1) % angles.len()]).rem_euclid(TAU);
+    if let Some(widest) = (0..targets.len()).max_by(|&i, &j| gap(i).total_cmp(&gap(j))) {
+        targets.rotate_left(widest);
+    }
+
+    let stops: Vec<_> = (0..santas)
+        .map(|i| {
+            let share = &targets[i * targets.len() / santas..(i + 1) * targets.len() / santas];
+            let mut route = nearest_neighbour(share);
+            two_opt(&mut route);
+            route
+        })
+        .collect();
+
+    let length = stops
+        .iter()
+        .map(|stops| {
+            stops
+                .iter()
+                .scan((0, 0), |position, &house| {
+                    Some(distance(mem::replace(position, house), house))
+                })
+                .sum::<usize>()
+        })
+        .sum();
+
+    Plan {
+        stops,
+        length,
+        lower_bound: spanning_tree_weight(&targets),
+    }
+}
+
+/// Route from the origin always going to the closest house not visited yet
+fn nearest_neighbour(houses: &[(isize, isize)]) -> Vec<(isize, isize)> {
+    let mut left = houses.to_vec();
+    let mut route = Vec::with_capacity(houses.len());
+    let mut position = (0, 0);
+    while let Some((i, _)) = left
+        .iter()
+        .enumerate()
+        .min_by_key(|&(_, &house)| distance(position, house))
+    {
+        position = left.swap_remove(i);
+        route.push(position);
+    }
+
+    route
+}
+
+/// Reverses parts of the route while that makes it shorter
+///
+/// The route starts at the origin and ends anywhere, so reversing up to the last house only
+/// changes a single move.
+fn two_opt(route: &mut [(isize, isize)]) {
+    let at = |route: &[(isize, isize)], i: usize| if i == 0 { (0, 0) } else { route[i - 1] };
+    let n = route.len();
+
+    let mut improved = true;
+    while improved {
+        improved = false;
+        // Reversing houses i..=j, counted from 1 after the origin
+        for i in 1..n {
+            for j in i + 1..=n {
+                let (before, first, last) = (at(route, i - 1), at(route, i), at(route, j));
+                let mut removed = distance(before, first);
+                let mut added = distance(before, last);
+                if j < n {
+                    let after = at(route, j + 1);
+                    removed += distance(last, after);
+                    added += distance(first, after);
+                }
+
+                if added < removed {
+                    route[i - 1..j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+}
+
+/// Weight of a minimum spanning tree over the origin and the houses, with Prim's algorithm
+///
+/// Any plan's routes together form a spanning tree, so none can be lighter.
+fn spanning_tree_weight(houses: &[(isize, isize)]) -> usize {
+    let mut closest: Vec<_> = houses
+        .iter()
+        .map(|&house| distance((0, 0), house))
+        .collect();
+    let mut in_tree = vec![false; houses.len()];
+    let mut weight = 0;
+    for _ in 0..houses.len() {
+        let (next, _) = closest
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| !in_tree[i])
+            .min_by_key(|&(_, &distance)| distance)
+            .unwrap();
+        in_tree[next] = true;
+        weight += closest[next];
+
+        for (i, house) in houses.iter().enumerate() {
+            closest[i] = closest[i].min(distance(houses[next], *house));
+        }
+    }
+
+    weight
+}
@@ -0,0 +1,57 @@
+use crate::{Fleet, House, Move, Santa};
+
+/// A single move of a route, for playing it back
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RouteStep {
+    /// 1-based, as step 0 is every deliverer at the starting house
+    pub step: usize,
+    /// Index of the deliverer who moved
+    pub deliverer: usize,
+    pub position: House,
+    /// Whether this deliverer hadn't visited the house yet
+    pub newly_visited: bool,
+}
+
+impl Santa {
+    /// Follows the moves one at a time, yielding every step as deliverer 0
+    pub fn route<'a, I: IntoIterator<Item = Move>>(
+        &'a mut self,
+        moves: I,
+    ) -> impl Iterator<Item = RouteStep> + 'a
+    where
+        I::IntoIter: 'a,
+    {
+        moves
+            .into_iter()
+            .enumerate()
+            .map(move |(i, step)| RouteStep {
+                step: i + 1,
+                deliverer: 0,
+                newly_visited: self.move_by(step),
+                position: self.position(),
+            })
+    }
+}
+
+impl Fleet {
+    /// Follows the moves one at a time, dispatching them like `move_by`, and yielding every step
+    pub fn route<'a, I: IntoIterator<Item = Move>>(
+        &'a mut self,
+        moves: I,
+    ) -> impl Iterator<Item = RouteStep> + 'a
+    where
+        I::IntoIter: 'a,
+    {
+        moves.into_iter().enumerate().map(move |(i, step)| {
+            let deliverer = self.current();
+            let newly_visited = self.move_by(step);
+
+            RouteStep {
+                step: i + 1,
+                deliverer,
+                position: self.santas()[deliverer].position(),
+                newly_visited,
+            }
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Direction, House, Move};
+
+#[derive(Clone, Copy, Debug)]
+struct Visits {
+    count: usize,
+    first_step: usize,
+    last_step: usize,
+}
+
+/// Smallest box of houses containing every visited one, only one house high unless moving in 3D
+#[derive(Clone, Copy, Debug)]
+pub struct BoundingBox {
+    pub min: House,
+    pub max: House,
+}
+
+impl BoundingBox {
+    /// Number of houses in the box, or `None` if there are more than a `usize` can count
+    pub fn area(&self) -> Option<usize> {
+        let side = |min: isize, max: isize| max.abs_diff(min).checked_add(1);
+
+        side(self.min.0, self.max.0)?
+            .checked_mul(side(self.min.1, self.max.1)?)?
+            .checked_mul(side(self.min.2, self.max.2)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct DeliveryStatistics {
+    /// The house that got the most presents, the first reached of them on a tie, and how many
+    pub most_visited: (House, usize),
+    /// Number of houses that got each number of presents
+    pub distribution: BTreeMap<usize, usize>,
+    /// The first house to get a second present, and the step it happened on
+    pub first_revisit: Option<(House, usize)>,
+    /// Number of times the path came back to a house
+    pub loops: usize,
+    /// Fewest and most steps it took to come back to a house
+    pub loop_lengths: Option<(usize, usize)>,
+    pub bounding_box: BoundingBox,
+    /// Fraction of the houses in the bounding box that got a present, `None` when they're too
+    /// many to count
+    pub coverage: Option<f64>,
+}
+
+/// Same as `Santa`, but counting the presents every house gets, and when
+pub struct CountingSanta {
+    position: House,
+    step: usize,
+    visits: HashMap<House, Visits>,
+    first_revisit: Option<(House, usize)>,
+    loop_lengths: Vec<usize>,
+}
+
+impl Default for CountingSanta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CountingSanta {
+    pub fn new() -> Self {
+        CountingSanta {
+            position: (0, 0, 0),
+            step: 0,
+            visits: HashMap::from([(
+                (0, 0, 0),
+                Visits {
+                    count: 1,
+                    first_step: 0,
+                    last_step: 0,
+                },
+            )]),
+            first_revisit: None,
+            loop_lengths: Vec::new(),
+        }
+    }
+
+    pub fn move_sleigh(&mut self, direction: &Direction) {
+        self.move_by(Move::from(direction));
+    }
+
+    /// Same as `move_sleigh`, for a move of any move set
+    pub fn move_by(&mut self, step: Move) {
+        let (x, y, z) = self.position;
+        self.position = (x + step.x, y + step.y, z + step.z);
+        self.step += 1;
+
+        let step = self.step;
+        let visits = self.visits.entry(self.position).or_insert(Visits {
+            count: 0,
+            first_step: step,
+            last_step: step,
+        });
+        if visits.count > 0 {
+            // Coming back to a house closes a loop since the last visit
+            self.loop_lengths.push(step - visits.last_step);
+            self.first_revisit.get_or_insert((self.position, step));
+        }
+        visits.count += 1;
+        visits.last_step = step;
+    }
+
+    /// Number of presents the house got
+    pub fn presents(&self, house: House) -> usize {
+        self.visits.get(&house).map_or(0, |visits| visits.count)
+    }
+
+    pub fn statistics(&self) -> DeliveryStatistics {
+        let mut distribution = BTreeMap::new();
+        for visits in self.visits.values() {
+            *distribution.entry(visits.count).or_default() += 1;
+        }
+
+        let (&most_visited, visits) = self
+            .visits
+            .iter()
+            .max_by_key(|(_, visits)| (visits.count, std::cmp::Reverse(visits.first_step)))
+            .expect("The starting house is always visited");
+
+        let mut bounding_box = BoundingBox {
+            min: (0, 0, 0),
+            max: (0, 0, 0),
+        };
+        for &(x, y, z) in self.visits.keys() {
+            let BoundingBox { min, max } = &mut bounding_box;
+            *min = (min.0.min(x), min.1.min(y), min.2.min(z));
+            *max = (max.0.max(x), max.1.max(y), max.2.max(z));
+        }
+
+        DeliveryStatistics {
+            most_visited: (most_visited, visits.count),
+            distribution,
+            first_revisit: self.first_revisit,
+            loops: self.loop_lengths.len(),
+            loop_lengths: self
+                .loop_lengths
+                .iter()
+                .min()
+                .zip(self.loop_lengths.iter().max())
+                .map(|(&shortest, &longest)| (shortest, longest)),
+            bounding_box,
+            coverage: bounding_box
+                .area()
+                .map(|area| self.visits.len() as f64 / area as f64),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Instant;
+
+use common::check::{self, Problem};
+use common::{Answer, Report};
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<String> {
+    // Open input file, which only holds the secret key
+    let input = File::open(file_path)?;
+
+    parse_reader(input)
+}
+
+pub fn parse_reader<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    Ok(input.trim().to_string())
+}
+
+/// Reports a missing secret key, or a key spanning several lines or containing whitespace
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = check::check_line_endings(input);
+
+    let lines: Vec<_> = input.lines().collect();
+    match lines.first() {
+        None => problems.push(Problem::new(1, 1, "Missing secret key")),
+        Some(key) if key.trim().is_empty() => {
+            problems.push(Problem::new(1, 1, "Missing secret key"))
+        }
+        Some(key) => {
+            if let Some(j) = key.trim().chars().position(char::is_whitespace) {
+                problems.push(Problem::new(
+                    1,
+                    check::column_of(key, key.trim_start()) + j,
+                    "Whitespace inside secret key",
+                ));
+            }
+        }
+    }
+
+    if lines.len() > 1 {
+        problems.push(Problem::new(
+            2,
+            1,
+            format!("Expected a single line, found {}", lines.len()),
+        ));
+    }
+
+    problems
+}
+
+/// Returns the lowest numbers that produce a hash starting with five and six zeros, respectively
+pub fn solve(secret_key: &str) -> (usize, usize) {
+    let mut suffix_number = 0;
+    let mut five_zeros_number = None;
+    let mut six_zeros_number = None;
+    loop {
+        let md5_input = format!("{}{}", secret_key, suffix_number);
+        let md5_hash = format!("{:x}", md5::compute(md5_input));
+
+        if &md5_hash[..5] == "00000" {
+            if five_zeros_number.is_none() {
+                five_zeros_number = Some(suffix_number);
+            }
+
+            if md5_hash.chars().nth(5).unwrap() == '0' && six_zeros_number.is_none() {
+                six_zeros_number = Some(suffix_number);
+            }
+        }
+
+        if five_zeros_number.is_some() && six_zeros_number.is_some() {
+            break;
+        }
+
+        suffix_number += 1;
+    }
+
+    (five_zeros_number.unwrap(), six_zeros_number.unwrap())
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let secret_key = parse_input(file_path)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (five_zeros_number, six_zeros_number) = solve(&secret_key);
+    let solution_time = t1.elapsed();
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new("Lowest five zeros number", five_zeros_number),
+            Answer::new("Lowest six zeros number", six_zeros_number),
+        ],
+    })
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::time::Instant;
+
+use common::check::{self, Problem};
+use common::{Answer, Report};
+
+use itertools::Itertools;
+
+fn is_vowel(c: &char) -> bool {
+    let vowels = ['a', 'e', 'i', 'o', 'u'];
+
+    vowels.iter().any(|v| v == c)
+}
+
+pub fn is_nice_string_1<T: AsRef<str>>(string: &T) -> bool {
+    // Nice strings countain at least 3 vowels
+    let vowel_count = string.as_ref().chars().filter(is_vowel).count();
+    if vowel_count < 3 {
+        return false;
+    }
+
+    // Nice strings have at least one occurrence of the same letter twice in a row
+    let has_repeated_letter = string
+        .as_ref()
+        .chars()
+        .tuple_windows()
+        .any(|(c1, c2)| c1 == c2);
+    if !has_repeated_letter {
+        return false;
+    }
+
+    // Nice strings can't have any of the substrings below
+    let naughty_substrs = ["ab", "cd", "pq", "xy"];
+    let has_naughty_substr = naughty_substrs
+        .iter()
+        .any(|&substr| string.as_ref().contains(substr));
+    if has_naughty_substr {
+        return false;
+    }
+
+    true
+}
+
+pub fn is_nice_string_2<T: AsRef<str>>(string: &T) -> bool {
+    // Nice strings have at least one pair of characters that repeat
+    let mut has_repeating_pairs = false;
+    let mut pairs_map = HashMap::new();
+    string
+        .as_ref()
+        .chars()
+        .tuple_windows()
+        .enumerate()
+        .for_each(|(i, (c1, c2))| {
+            if let Some(pos) = pairs_map.get(&(c1, c2)) {
+                if i > pos + 1 {
+                    has_repeating_pairs = true;
+                }
+                return;
+            }
+
+            pairs_map.insert((c1, c2), i);
+        });
+    if !has_repeating_pairs {
+        return false;
+    }
+
+    // Nice strings have at least one letter which repeats with exactly one letter between them
+    let has_repeated_letter = string
+        .as_ref()
+        .chars()
+        .tuple_windows()
+        .any(|(c1, _c2, c3)| c1 == c3);
+    if !has_repeated_letter {
+        return false;
+    }
+
+    true
+}
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<Vec<String>> {
+    // Open input file
+    let input = File::open(file_path)?;
+
+    parse_reader(BufReader::new(input))
+}
+
+pub fn parse_reader<R: BufRead>(reader: R) -> io::Result<Vec<String>> {
+    parse_stream(reader).collect()
+}
+
+/// Lazily reads the strings one line at a time, so inputs of any size take constant memory
+pub fn parse_stream<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<String>> {
+    reader.lines()
+}
+
+/// Reports empty lines and characters other than lowercase letters, without solving
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = check::check_line_endings(input);
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            problems.push(Problem::new(i + 1, 1, "Empty line"));
+            continue;
+        }
+
+        for (j, c) in line.chars().enumerate() {
+            if !c.is_ascii_lowercase() {
+                problems.push(Problem::new(
+                    i + 1,
+                    j + 1,
+                    format!("Invalid character {:?}", c),
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Returns the number of nice strings under the old and the new rules
+pub fn solve(strings: &[String]) -> (usize, usize) {
+    let nice_strings_count_1 = strings.iter().filter(is_nice_string_1).count();
+    let nice_strings_count_2 = strings.iter().filter(is_nice_string_2).count();
+
+    (nice_strings_count_1, nice_strings_count_2)
+}
+
+/// Same as `solve`, but over a stream of strings, stopping at the first error
+pub fn solve_stream<I: IntoIterator<Item = io::Result<String>>>(
+    strings: I,
+) -> io::Result<(usize, usize)> {
+    let mut nice_strings_count_1 = 0;
+    let mut nice_strings_count_2 = 0;
+    for string in strings {
+        let string = string?;
+
+        if is_nice_string_1(&string) {
+            nice_strings_count_1 += 1;
+        }
+        if is_nice_string_2(&string) {
+            nice_strings_count_2 += 1;
+        }
+    }
+
+    Ok((nice_strings_count_1, nice_strings_count_2))
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let strings = parse_input(file_path)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (nice_strings_count_1, nice_strings_count_2) = solve(&strings);
+    let solution_time = t1.elapsed();
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new(
+                "Number of nice strings using old rules",
+                nice_strings_count_1,
+            ),
+            Answer::new(
+                "Number of nice strings using new rules",
+                nice_strings_count_2,
+            ),
+        ],
+    })
+}
@@ -0,0 +1,8 @@
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let report = aoc2015_day05::run("years/2015/day05/input")?;
+    report.print();
+
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::time::Instant;
+
+use common::check::{self, Problem};
+use common::{Answer, Report};
+
+use regex::Regex;
+
+pub static GRID_SIZE: usize = 1000;
+
+pub type Coordinate = (usize, usize);
+pub type Rectangle = (Coordinate, Coordinate);
+
+#[derive(Debug)]
+pub enum Instruction {
+    TurnOn(Rectangle),
+    TurnOff(Rectangle),
+    Toggle(Rectangle),
+}
+
+pub enum Ruleset {
+    Part1,
+    Part2,
+}
+
+pub struct LightGrid {
+    ruleset: Ruleset,
+    grid: Vec<usize>,
+}
+
+impl LightGrid {
+    pub fn new(ruleset: Ruleset) -> Self {
+        LightGrid {
+            ruleset,
+            grid: vec![0; GRID_SIZE * GRID_SIZE],
+        }
+    }
+
+    pub fn run_instruction(&mut self, instruction: &Instruction) {
+        match self.ruleset {
+            Ruleset::Part1 => match *instruction {
+                Instruction::TurnOn(((x1, y1), (x2, y2))) => {
+                    for x in x1..=x2 {
+                        for y in y1..=y2 {
+                            self.grid[x * GRID_SIZE + y] = 1;
+                        }
+                    }
+                }
+                Instruction::TurnOff(((x1, y1), (x2, y2))) => {
+                    for x in x1..=x2 {
+                        for y in y1..=y2 {
+                            self.grid[x * GRID_SIZE + y] = 0;
+                        }
+                    }
+                }
+                Instruction::Toggle(((x1, y1), (x2, y2))) => {
+                    for x in x1..=x2 {
+                        for y in y1..=y2 {
+                            self.grid[x * GRID_SIZE + y] += 1;
+                            self.grid[x * GRID_SIZE + y] %= 2;
+                        }
+                    }
+                }
+            },
+            Ruleset::Part2 => match *instruction {
+                Instruction::TurnOn(((x1, y1), (x2, y2))) => {
+                    for x in x1..=x2 {
+                        for y in y1..=y2 {
+                            self.grid[x * GRID_SIZE + y] += 1;
+                        }
+                    }
+                }
+                Instruction::TurnOff(((x1, y1), (x2, y2))) => {
+                    for x in x1..=x2 {
+                        for y in y1..=y2 {
+                            self.grid[x * GRID_SIZE + y] =
+                                self.grid[x * GRID_SIZE + y].saturating_sub(1);
+                        }
+                    }
+                }
+                Instruction::Toggle(((x1, y1), (x2, y2))) => {
+                    for x in x1..=x2 {
+                        for y in y1..=y2 {
+                            self.grid[x * GRID_SIZE + y] += 2;
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
+
+fn instruction_regex() -> Regex {
+    Regex::new(
+        r"^(?<instruction>toggle|turn on|turn off)\s+(?<x1>\d+),(?<y1>\d+)\s+through\s+(?<x2>\d+),(?<y2>\d+)$" 
+    )
+    .unwrap()
+}
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<Vec<Instruction>> {
+    // Open input file
+    let input = File::open(file_path)?;
+
+    parse_reader(BufReader::new(input))
+}
+
+pub fn parse_reader<R: BufRead>(reader: R) -> io::Result<Vec<Instruction>> {
+    parse_stream(reader).collect()
+}
+
+/// Lazily parses the instructions one line at a time, so inputs of any size take constant memory
+pub fn parse_stream<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<Instruction>> {
+    let regx = instruction_regex();
+
+    reader
+        .lines()
+        .map(move |line| parse_instruction(&line?, &regx))
+}
+
+fn parse_instruction(line: &str, regx: &Regex) -> io::Result<Instruction> {
+    let caps = regx.captures(line).ok_or(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "Invalid input format",
+    ))?;
+
+    let instruction_str = caps["instruction"].trim();
+    let x1: usize = caps["x1"]
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let y1: usize = caps["y1"]
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let x2: usize = caps["x2"]
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let y2: usize = caps["y2"]
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    if [x1, y1, x2, y2].iter().any(|&c| c >= GRID_SIZE) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Coordinate outside the grid",
+        ));
+    }
+
+    let instruction = match instruction_str {
+        "turn on" => Instruction::TurnOn(((x1, y1), (x2, y2))),
+        "turn off" => Instruction::TurnOff(((x1, y1), (x2, y2))),
+        "toggle" => Instruction::Toggle(((x1, y1), (x2, y2))),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid instruction",
+            ))
+        }
+    };
+
+    Ok(instruction)
+}
+
+/// Reports malformed instructions, coordinates outside the grid and inverted rectangles, without
+/// solving
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = check::check_line_endings(input);
+
+    let regx = instruction_regex();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        let Some(caps) = regx.captures(line) else {
+            problems.push(Problem::new(i + 1, 1, "Invalid instruction format"));
+            continue;
+        };
+
+        let mut coordinates = Vec::new();
+        for name in ["x1", "y1", "x2", "y2"] {
+            let field = caps.name(name).unwrap();
+            let column = field.start() + 1;
+            match field.as_str().parse::<usize>() {
+                Ok(value) if value >= GRID_SIZE => {
+                    problems.push(Problem::new(
+                        i + 1,
+                        column,
+                        format!(
+                            "Coordinate {} = {} is outside the {}x{} grid",
+                            name, value, GRID_SIZE, GRID_SIZE
+                        ),
+                    ));
+                    coordinates.push(Some((value, column)));
+                }
+                Ok(value) => coordinates.push(Some((value, column))),
+                Err(e) => {
+                    problems.push(Problem::new(
+                        i + 1,
+                        column,
+                        format!("Invalid coordinate {}: {}", name, e),
+                    ));
+                    coordinates.push(None);
+                }
+            }
+        }
+
+        if let [Some((x1, column)), _, Some((x2, _)), _] = coordinates[..] {
+            if x1 > x2 {
+                problems.push(Problem::new(
+                    i + 1,
+                    column,
+                    format!("Rectangle has x1 > x2 ({} > {})", x1, x2),
+                ));
+            }
+        }
+        if let [_, Some((y1, column)), _, Some((y2, _))] = coordinates[..] {
+            if y1 > y2 {
+                problems.push(Problem::new(
+                    i + 1,
+                    column,
+                    format!("Rectangle has y1 > y2 ({} > {})", y1, y2),
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Returns the number of lights lit under the first ruleset and the total brightness under the
+/// second
+pub fn solve(instructions: &[Instruction]) -> (usize, usize) {
+    let mut light_grid_1 = LightGrid::new(Ruleset::Part1);
+    let mut light_grid_2 = LightGrid::new(Ruleset::Part2);
+
+    instructions
+        .iter()
+        .for_each(|i| light_grid_1.run_instruction(i));
+    let lit_count = light_grid_1.grid.iter().filter(|&&s| s == 1).count();
+
+    instructions
+        .iter()
+        .for_each(|i| light_grid_2.run_instruction(i));
+    let total_brightness: usize = light_grid_2.grid.iter().sum();
+
+    (lit_count, total_brightness)
+}
+
+/// Same as `solve`, but over a stream of instructions, stopping at the first error
+pub fn solve_stream<I: IntoIterator<Item = io::Result<Instruction>>>(
+    instructions: I,
+) -> io::Result<(usize, usize)> {
+    let mut light_grid_1 = LightGrid::new(Ruleset::Part1);
+    let mut light_grid_2 = LightGrid::new(Ruleset::Part2);
+
+    for instruction in instructions {
+        let instruction = instruction?;
+
+        light_grid_1.run_instruction(&instruction);
+        light_grid_2.run_instruction(&instruction);
+    }
+
+    let lit_count = light_grid_1.grid.iter().filter(|&&s| s == 1).count();
+    let total_brightness: usize = light_grid_2.grid.iter().sum();
+
+    Ok((lit_count, total_brightness))
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let instructions = parse_input(file_path)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (lit_count, total_brightness) = solve(&instructions);
+    let solution_time = t1.elapsed();
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new("Number of lights lit", lit_count),
+            Answer::new("Total brightness", total_brightness),
+        ],
+    })
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Instant;
+
+use common::check::{self, Problem};
+use common::{Answer, Report};
+
+#[derive(Debug)]
+pub enum Operand {
+    Address(String),
+    Number(isize),
+}
+
+impl FromStr for Operand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(number) = s.parse() {
+            Ok(Self::Number(number))
+        } else {
+            Ok(Self::Address(s.to_string()))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Operation {
+    Assign(Operand),
+    Not(Operand),
+    And(Operand, Operand),
+    Or(Operand, Operand),
+    LShift(Operand, Operand),
+    RShift(Operand, Operand),
+}
+
+impl Operation {
+    fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Operation::Assign(operand) | Operation::Not(operand) => vec![operand],
+            Operation::And(operand1, operand2)
+            | Operation::Or(operand1, operand2)
+            | Operation::LShift(operand1, operand2)
+            | Operation::RShift(operand1, operand2) => vec![operand1, operand2],
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Option<(&str, Operation)> {
+    let operands: Vec<&str> = line.split_ascii_whitespace().collect();
+    let (target, operation) = match operands[..] {
+        [number, "->", target] => (
+            target,
+            Operation::Assign(Operand::from_str(number).unwrap()),
+        ),
+        ["NOT", operand, "->", target] => {
+            (target, Operation::Not(Operand::from_str(operand).unwrap()))
+        }
+        [operand1, "AND", operand2, "->", target] => (
+            target,
+            Operation::And(
+                Operand::from_str(operand1).unwrap(),
+                Operand::from_str(operand2).unwrap(),
+            ),
+        ),
+        [operand1, "OR", operand2, "->", target] => (
+            target,
+            Operation::Or(
+                Operand::from_str(operand1).unwrap(),
+                Operand::from_str(operand2).unwrap(),
+            ),
+        ),
+        [operand, "LSHIFT", number, "->", target] => (
+            target,
+            Operation::LShift(
+                Operand::from_str(operand).unwrap(),
+                Operand::from_str(number).unwrap(),
+            ),
+        ),
+        [operand, "RSHIFT", number, "->", target] => (
+            target,
+            Operation::RShift(
+                Operand::from_str(operand).unwrap(),
+                Operand::from_str(number).unwrap(),
+            ),
+        ),
+        _ => return None,
+    };
+
+    Some((target, operation))
+}
+
+pub fn parse_input<T: AsRef<Path>>(file_path: T) -> io::Result<HashMap<String, Operation>> {
+    // Open input file
+    let input = File::open(file_path)?;
+
+    parse_reader(BufReader::new(input))
+}
+
+pub fn parse_reader<R: BufRead>(reader: R) -> io::Result<HashMap<String, Operation>> {
+    let mut circuit = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+
+        let (target, operation) = parse_line(&line)
+            .ok_or(io::Error::new(io::ErrorKind::InvalidInput, "Invalid input"))?;
+
+        circuit.insert(target.to_string(), operation);
+    }
+
+    Ok(circuit)
+}
+
+pub fn get_final_wire_value(
+    wire: &str,
+    circuit: &HashMap<String, Operation>,
+    lookup_table: &mut HashMap<String, isize>,
+) -> isize {
+    if let Some(value) = lookup_table.get(wire) {
+        *value
+    } else {
+        let value = match circuit.get(wire).unwrap() {
+            Operation::Assign(operand) => {
+                match operand {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                }
+            }
+            Operation::Not(operand) => {
+                let number = match operand {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                !number
+            }
+            Operation::And(operand1, operand2) => {
+                let number1 = match operand1 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                let number2 = match operand2 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                number1 & number2
+            }
+            Operation::Or(operand1, operand2) => {
+                let number1 = match operand1 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                let number2 = match operand2 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                number1 | number2
+            }
+            Operation::LShift(operand1, operand2) => {
+                let number1 = match operand1 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                let number2 = match operand2 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                number1 << number2
+            }
+            Operation::RShift(operand1, operand2) => {
+                let number1 = match operand1 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                let number2 = match operand2 {
+                    Operand::Address(wire) => get_final_wire_value(wire, circuit, lookup_table),
+                    Operand::Number(number) => *number,
+                };
+
+                number1 >> number2
+            }
+        };
+
+        lookup_table.insert(wire.to_string(), value);
+
+        value
+    }
+}
+
+/// Reports malformed gates, out of range signals, and wires that are driven twice or never driven,
+/// without solving
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems = check::check_line_endings(input);
+
+    // Line and column where each wire is first driven and first referenced
+    let mut drivers: HashMap<&str, (usize, usize)> = HashMap::new();
+    let mut references: HashMap<&str, (usize, usize)> = HashMap::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        let Some((target, operation)) = parse_line(line) else {
+            problems.push(Problem::new(i + 1, 1, "Invalid gate format"));
+            continue;
+        };
+
+        let target_column = check::column_of(line, target);
+        if target.parse::<isize>().is_ok() {
+            problems.push(Problem::new(
+                i + 1,
+                target_column,
+                format!("Signal {} can't be driven", target),
+            ));
+        } else if let Some((first_line, _)) = drivers.get(target) {
+            problems.push(Problem::new(
+                i + 1,
+                target_column,
+                format!(
+                    "Wire {:?} is driven twice, first on line {}",
+                    target, first_line
+                ),
+            ));
+        } else {
+            drivers.insert(target, (i + 1, target_column));
+        }
+
+        let tokens: Vec<&str> = line.split_ascii_whitespace().collect();
+        for operand in operation.operands() {
+            match operand {
+                Operand::Number(number) if !(0..=u16::MAX as isize).contains(number) => {
+                    let token = tokens
+                        .iter()
+                        .find(|t| t.parse::<isize>() == Ok(*number))
+                        .unwrap();
+                    problems.push(Problem::new(
+                        i + 1,
+                        check::column_of(line, token),
+                        format!("Signal {} doesn't fit in 16 bits", number),
+                    ));
+                }
+                Operand::Number(_) => (),
+                Operand::Address(wire) => {
+                    let token = tokens.iter().find(|&&t| t == wire).unwrap();
+                    references
+                        .entry(token)
+                        .or_insert((i + 1, check::column_of(line, token)));
+                }
+            }
+        }
+    }
+
+    let mut undriven: Vec<_> = references
+        .iter()
+        .filter(|(wire, _)| !drivers.contains_key(*wire))
+        .collect();
+    undriven.sort_by_key(|(_, &position)| position);
+    for (wire, &(line, column)) in undriven {
+        problems.push(Problem::new(
+            line,
+            column,
+            format!("Wire {:?} is referenced but never driven", wire),
+        ));
+    }
+
+    for wire in ["a", "b"] {
+        if !drivers.contains_key(wire) {
+            problems.push(Problem::new(
+                1,
+                1,
+                format!("Wire {:?} is never driven", wire),
+            ));
+        }
+    }
+
+    problems
+}
+
+/// Returns the value on wire 'a', and the value on wire 'a' after overriding wire 'b' with it
+pub fn solve(circuit: &mut HashMap<String, Operation>) -> (isize, isize) {
+    // Get value on 'a' wire
+    let mut values_table = HashMap::new();
+    let wire_a_value_1 = get_final_wire_value("a", circuit, &mut values_table);
+
+    // Set 'b' wire to the value of a, and re-run
+    *circuit.get_mut("b").unwrap() = Operation::Assign(Operand::Number(wire_a_value_1));
+    values_table.clear();
+    let wire_a_value_2 = get_final_wire_value("a", circuit, &mut values_table);
+
+    (wire_a_value_1, wire_a_value_2)
+}
+
+pub fn run<T: AsRef<Path>>(file_path: T) -> Result<Report, Box<dyn Error>> {
+    // Parse the input and time it
+    let t0 = Instant::now();
+    let mut circuit = parse_input(file_path)?;
+    let parse_time = t0.elapsed();
+
+    // Compute solution and time it
+    let t1 = Instant::now();
+    let (wire_a_value_1, wire_a_value_2) = solve(&mut circuit);
+    let solution_time = t1.elapsed();
+
+    Ok(Report {
+        parse_time,
+        solution_time,
+        answers: vec![
+            Answer::new("First value on wire 'a'", wire_a_value_1),
+            Answer::new("Second value on wire 'a'", wire_a_value_2),
+        ],
+    })
+}